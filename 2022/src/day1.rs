@@ -1,3 +1,8 @@
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameter, Parameters, Solution};
+
 fn read_elves(input: &str) -> Vec<i32> {
     let mut ret = vec![];
     let mut current_elf = 0;
@@ -25,6 +30,28 @@ pub fn part2(input: &str, count: usize) -> i32 {
     elves.iter().rev().take(count).sum()
 }
 
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 1;
+    const PART2_PARAMETERS: &'static [Parameter] = &[Parameter::new::<usize>("count", "3")];
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input, params.get("count"))))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,9 @@
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::ocr;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 pub fn part1(input: &str) -> i32 {
     let mut cycle = 0;
     let mut regx = 1;
@@ -27,7 +33,8 @@ pub fn part1(input: &str) -> i32 {
     total_signal_strength
 }
 
-pub fn part2(input: &str) -> String {
+/// Picture drawn on the CRT, with `#` for lit pixels
+pub fn crt_picture(input: &str) -> String {
     let mut cycle = 0;
     let mut regx = 1;
 
@@ -64,6 +71,32 @@ pub fn part2(input: &str) -> String {
     output
 }
 
+/// Letters drawn on the CRT, or the picture itself if they cannot be read
+pub fn part2(input: &str) -> String {
+    ocr::read_letters(&crt_picture(input))
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 10;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,7 +121,6 @@ mod tests {
             #######.......#######.......#######.....\n\
         ";
 
-        // EHZFZHCZ
         const INPUT_OUTPUT: &str = "\
             ####.#..#.####.####.####.#..#..##..####.\n\
             #....#..#....#.#.......#.#..#.#..#....#.\n\
@@ -98,7 +130,9 @@ mod tests {
             ####.#..#.####.#....####.#..#..##..####.\n\
         ";
 
+        assert_eq!(crt_picture(EXAMPLE), EXAMPLE_OUTPUT);
+        assert_eq!(crt_picture(INPUT), INPUT_OUTPUT);
         assert_eq!(part2(EXAMPLE), EXAMPLE_OUTPUT);
-        assert_eq!(part2(INPUT), INPUT_OUTPUT);
+        assert_eq!(part2(INPUT), "EHZFZHCZ");
     }
 }
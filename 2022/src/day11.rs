@@ -1,5 +1,9 @@
 use std::collections::BinaryHeap;
 use std::collections::VecDeque;
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 enum Operator {
     Plus,
@@ -139,7 +143,7 @@ fn round1(monkeys: &mut [Monkey]) {
             monkeys[id].inspection_count += 1;
             let monkey = &monkeys[id];
             let worry = monkey.operation.eval(item) / 3;
-            let monkey_id = if worry % monkey.divisor == 0 {
+            let monkey_id = if worry.is_multiple_of(monkey.divisor) {
                 monkey.if_true.0
             } else {
                 monkey.if_false.0
@@ -156,7 +160,7 @@ fn round2(monkeys: &mut [Monkey]) {
             monkeys[id].inspection_count += 1;
             let monkey = &monkeys[id];
             let worry = monkey.operation.eval(item) % modulo;
-            let monkey_id = if worry % monkey.divisor == 0 {
+            let monkey_id = if worry.is_multiple_of(monkey.divisor) {
                 monkey.if_true.0
             } else {
                 monkey.if_false.0
@@ -195,6 +199,27 @@ pub fn part2(input: &str) -> u64 {
     find_top_monkeys(input, 10_000, round2)
 }
 
+pub struct Day11;
+
+impl Solution for Day11 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 11;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,95 +1,58 @@
-use std::collections::HashSet;
-use std::collections::VecDeque;
-
-#[derive(Clone, Eq, Hash, PartialEq)]
-struct Coord(i64, i64);
-
-impl Coord {
-    fn neighbors(&self) -> Vec<Self> {
-        let &Coord(i, j) = self;
-        vec![
-            Coord(i - 1, j),
-            Coord(i, j - 1),
-            Coord(i, j + 1),
-            Coord(i + 1, j),
-        ]
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::grid::Grid;
+use aoc_common::search::bfs;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
+fn height(c: u8) -> u8 {
+    match c {
+        b'S' => b'a',
+        b'E' => b'z',
+        c => c,
     }
 }
 
-struct Grid(Vec<Vec<char>>);
-
-impl Grid {
-    fn read(input: &str) -> Self {
-        Grid(input.lines().map(|line| line.chars().collect()).collect())
-    }
-
-    fn contains(&self, coord: &Coord) -> bool {
-        let grid = &self.0;
-        let rows = grid.len() as i64;
-        let cols = grid[0].len() as i64;
-        0 <= coord.0 && coord.0 < rows && 0 <= coord.1 && coord.1 < cols
-    }
-
-    fn find_char(&self, needle: char) -> Option<Coord> {
-        for (i, row) in self.0.iter().enumerate() {
-            for (j, c) in row.iter().enumerate() {
-                if *c == needle {
-                    return Some(Coord(i as i64, j as i64));
-                }
-            }
-        }
-        None
-    }
-
-    fn char_at(&self, coord: &Coord) -> char {
-        let &Coord(i, j) = coord;
-        self.0[i as usize][j as usize]
-    }
+fn count_steps(input: &str, target: u8) -> Option<usize> {
+    let grid = Grid::parse(input).unwrap();
+    let start = grid.find(&b'E').unwrap();
+    // walk backwards from the end, so that part 2 can stop at the first low spot
+    let successors = |&pos: &(usize, usize)| {
+        let min_next_elevation = height(grid[pos]) - 1;
+        grid.neighbors4(pos)
+            .filter(|&next| height(grid[next]) >= min_next_elevation)
+            .collect::<Vec<_>>()
+    };
+    bfs([start], successors, |&pos| grid[pos] == target).goal_cost()
+}
 
-    fn height_at(&self, coord: &Coord) -> u32 {
-        match self.char_at(coord) {
-            'S' => 1,
-            'E' => 26,
-            c => 1 + (c as u32) - ('a' as u32),
-        }
-    }
+pub fn part1(input: &str) -> usize {
+    count_steps(input, b'S').unwrap()
 }
 
-fn count_steps(input: &str, target: char) -> Option<u32> {
-    let grid = Grid::read(input);
-    let start = grid.find_char('E').unwrap();
+pub fn part2(input: &str) -> usize {
+    count_steps(input, b'a').unwrap()
+}
 
-    let mut seen = HashSet::new();
-    let mut q = VecDeque::new();
-    q.push_back((0, start));
-    while let Some((steps, coord)) = q.pop_front() {
-        if seen.contains(&coord) {
-            continue;
-        }
-        seen.insert(coord.clone());
+pub struct Day12;
 
-        if grid.char_at(&coord) == target {
-            return Some(steps);
-        }
+impl Solution for Day12 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 12;
 
-        let min_next_elevation = grid.height_at(&coord) - 1;
+    type Input<'a> = &'a str;
 
-        for next in coord.neighbors() {
-            if !grid.contains(&next) || grid.height_at(&next) < min_next_elevation {
-                continue;
-            }
-            q.push_back((steps + 1, next));
-        }
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
     }
-    None
-}
 
-pub fn part1(input: &str) -> u32 {
-    count_steps(input, 'S').unwrap()
-}
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
 
-pub fn part2(input: &str) -> u32 {
-    count_steps(input, 'a').unwrap()
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,9 @@
 use std::cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd};
 use std::fmt;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 #[derive(Eq, PartialEq, Clone)]
 enum Value {
     Integer(u64),
@@ -113,6 +116,27 @@ pub fn part2(input: &str) -> usize {
     idx1 * idx2
 }
 
+pub struct Day13;
+
+impl Solution for Day13 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 13;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl fmt::Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl fmt::Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,7 +1,13 @@
 use std::cmp::{Eq, PartialEq};
 use std::collections::HashSet;
+use std::fmt::Display;
 
-#[derive(Eq, Hash, PartialEq)]
+use aoc_common::animation::Animation;
+use aoc_common::error::ParseError;
+use aoc_common::grid::Grid;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
+#[derive(Clone, Eq, Hash, PartialEq)]
 struct Coord {
     x: usize,
     y: usize,
@@ -32,6 +38,8 @@ impl Wall {
 struct World {
     occupied: HashSet<Coord>,
     lowest_wall: usize,
+    // rock, only needed to draw frames
+    walls: HashSet<Coord>,
 }
 
 impl World {
@@ -69,30 +77,43 @@ impl World {
         World {
             occupied,
             lowest_wall,
+            walls: HashSet::new(),
         }
     }
 
-    #[allow(dead_code)]
-    fn display(&self) {
-        let min_x = self.occupied.iter().map(|c| c.x).min().unwrap();
-        let max_x = self.occupied.iter().map(|c| c.x).max().unwrap();
-        let min_y = self.occupied.iter().map(|c| c.y).min().unwrap();
-        let max_y = self.occupied.iter().map(|c| c.y).max().unwrap();
-        for y in min_y..=max_y {
-            for x in min_x..=max_x {
-                let c = Coord { x, y };
-                if self.occupied.contains(&c) {
-                    print!("#");
-                } else {
-                    print!(".");
-                }
-            }
-            println!();
+    /// Rock as `#` and sand as `o`, in a fixed window which contains all the sand that can settle
+    fn frame(&self, with_bottom: bool) -> Grid<u8> {
+        // the window can extend left of x = 0
+        let xs = self.walls.iter().map(|c| c.x as i64).chain([500]);
+        let (min_x, max_x) = (xs.clone().min().unwrap(), xs.max().unwrap());
+        let (min_x, max_x, max_y) = if with_bottom {
+            // sand spreads in a cone above the floor
+            let max_y = self.lowest_wall as i64 + 2;
+            (min_x.min(500 - max_y), max_x.max(500 + max_y), max_y)
+        } else {
+            (min_x - 1, max_x + 1, self.lowest_wall as i64)
+        };
+        let column = |x: usize| (x as i64 - min_x) as usize;
+        let mut frame = Grid::new(max_y as usize + 1, (max_x - min_x + 1) as usize, b'.');
+        frame[(0, column(500))] = b'+';
+        for c in &self.occupied {
+            frame[(c.y, column(c.x))] = if self.walls.contains(c) { b'#' } else { b'o' };
+        }
+        if with_bottom {
+            frame.row_mut(max_y as usize).fill(b'#');
+        }
+        frame
+    }
+
+    fn record(&self, animation: &mut Option<&mut Animation>, with_bottom: bool) {
+        if let Some(animation) = animation {
+            let units = self.occupied.len() - self.walls.len();
+            animation.push(format!("After {units} units:"), &self.frame(with_bottom));
         }
     }
 
-    fn fill_with_sand_without_bottom(&mut self) -> u64 {
-        fn aux(world: &mut World, c: Coord) -> (u64, bool) {
+    fn fill_with_sand_without_bottom(&mut self, mut animation: Option<&mut Animation>) -> u64 {
+        fn aux(world: &mut World, c: Coord, animation: &mut Option<&mut Animation>) -> (u64, bool) {
             if world.occupied.contains(&c) {
                 return (0, true);
             }
@@ -100,7 +121,7 @@ impl World {
                 return (0, false);
             }
             let mut ret = 0;
-            let (below, stable) = aux(world, Coord { x: c.x, y: c.y + 1 });
+            let (below, stable) = aux(world, Coord { x: c.x, y: c.y + 1 }, animation);
             ret += below;
             if !stable {
                 return (ret, false);
@@ -111,6 +132,7 @@ impl World {
                     x: c.x - 1,
                     y: c.y + 1,
                 },
+                animation,
             );
             ret += left;
             if !stable {
@@ -122,52 +144,94 @@ impl World {
                     x: c.x + 1,
                     y: c.y + 1,
                 },
+                animation,
             );
             ret += right;
             if !stable {
                 return (ret, false);
             }
             world.occupied.insert(c);
+            world.record(animation, false);
             ret += 1;
             (ret, true)
         }
-        let (count, stable) = aux(self, Coord { x: 500, y: 0 });
+        self.record(&mut animation, false);
+        let (count, stable) = aux(self, Coord { x: 500, y: 0 }, &mut animation);
         assert!(!stable);
         count
     }
 
-    fn fill_with_sand_with_bottom(&mut self) -> u64 {
-        fn fill_from(world: &mut World, c: Coord) -> u64 {
+    fn fill_with_sand_with_bottom(&mut self, mut animation: Option<&mut Animation>) -> u64 {
+        fn fill_from(world: &mut World, c: Coord, animation: &mut Option<&mut Animation>) -> u64 {
             if world.occupied.contains(&c) || c.y == world.lowest_wall + 2 {
                 return 0;
             }
             let mut ret = 0;
             let below = Coord { x: c.x, y: c.y + 1 };
-            ret += fill_from(world, below);
+            ret += fill_from(world, below, animation);
             let left = Coord {
                 x: c.x - 1,
                 y: c.y + 1,
             };
-            ret += fill_from(world, left);
+            ret += fill_from(world, left, animation);
             let right = Coord {
                 x: c.x + 1,
                 y: c.y + 1,
             };
-            ret += fill_from(world, right);
+            ret += fill_from(world, right, animation);
             world.occupied.insert(c);
+            world.record(animation, true);
             ret += 1;
             ret
         }
-        fill_from(self, Coord { x: 500, y: 0 })
+        self.record(&mut animation, true);
+        fill_from(self, Coord { x: 500, y: 0 }, &mut animation)
     }
 }
 
 pub fn part1(input: &str) -> u64 {
-    World::read(input).fill_with_sand_without_bottom()
+    World::read(input).fill_with_sand_without_bottom(None)
 }
 
 pub fn part2(input: &str) -> u64 {
-    World::read(input).fill_with_sand_with_bottom()
+    World::read(input).fill_with_sand_with_bottom(None)
+}
+
+/// Cave each time a unit of sand comes to rest
+pub fn animate(input: &str, with_bottom: bool) -> Animation {
+    let mut animation = Animation::new()
+        .with_color('#', [128, 128, 128])
+        .with_color('o', [255, 208, 96])
+        .with_color('+', [255, 64, 64]);
+    let mut world = World::read(input);
+    world.walls = world.occupied.clone();
+    if with_bottom {
+        world.fill_with_sand_with_bottom(Some(&mut animation));
+    } else {
+        world.fill_with_sand_without_bottom(Some(&mut animation));
+    }
+    animation
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 14;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
 }
 
 #[cfg(test)]
@@ -188,4 +252,48 @@ mod tests {
         assert_eq!(part2(EXAMPLE), 93);
         assert_eq!(part2(INPUT), 26484);
     }
+
+    #[test]
+    fn test_animate() {
+        let animation = animate(EXAMPLE, false);
+        assert_eq!(animation.len(), 25);
+        assert_eq!(
+            animation.frame(24).to_string(),
+            "\
+            .......+....\n\
+            ............\n\
+            .......o....\n\
+            ......ooo...\n\
+            .....#ooo##.\n\
+            ....o#ooo#..\n\
+            ...###ooo#..\n\
+            .....oooo#..\n\
+            ..o.ooooo#..\n\
+            .#########..\n\
+            "
+        );
+        assert_eq!(animate(EXAMPLE, true).len(), 94);
+    }
+
+    #[test]
+    fn test_frame_bounds() {
+        // the windows extend left of x = 0
+        let mut world = World::read("0,3 -> 2,3");
+        world.walls = world.occupied.clone();
+        let frame = world.frame(false);
+        assert_eq!((frame.rows(), frame.cols()), (4, 503));
+        assert_eq!(frame[(0, 501)], b'+');
+        assert_eq!(frame[(3, 1)], b'#');
+
+        let mut world = World::read("499,600 -> 501,600");
+        world.walls = world.occupied.clone();
+        let frame = world.frame(true);
+        assert_eq!((frame.rows(), frame.cols()), (603, 1205));
+        assert_eq!(frame[(0, 602)], b'+');
+
+        // walls outside of the sand cone
+        let animation = animate("400,3 -> 402,3\n", true);
+        assert_eq!(animation.frame(0).cols(), 106);
+        assert_eq!(animation.frame(0)[(3, 0)], b'#');
+    }
 }
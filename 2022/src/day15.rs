@@ -1,4 +1,10 @@
-use std::cmp::Ord;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::ops::Range;
+
+use aoc_common::error::ParseError;
+use aoc_common::interval::IntervalSet;
+use aoc_common::solution::{Answer, Parameter, Parameters, Solution};
 
 #[derive(Eq, Hash, PartialEq)]
 struct Coord {
@@ -25,6 +31,7 @@ impl Coord {
 
 struct Sensor {
     position: Coord,
+    beacon: Coord,
     cell_radius: u64,
 }
 
@@ -36,107 +43,69 @@ impl Sensor {
         let radius = position.distance(&beacon);
         Sensor {
             position,
+            beacon,
             cell_radius: radius,
         }
     }
-}
-
-#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
-enum BoundType {
-    Open,
-    Close,
-}
 
-#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
-struct IntervalBound {
-    position: i64,
-    kind: BoundType,
-}
-
-impl IntervalBound {
-    fn open(position: i64) -> Self {
-        IntervalBound {
-            position,
-            kind: BoundType::Open,
-        }
-    }
-    fn close(position: i64) -> Self {
-        IntervalBound {
-            position,
-            kind: BoundType::Close,
+    /// Cells of row `y` closer to the sensor than its beacon, or as close
+    fn covered(&self, y: i64) -> Option<Range<i64>> {
+        let dy = self.position.y.abs_diff(y);
+        if self.cell_radius < dy {
+            return None;
         }
+        let rem = (self.cell_radius - dy) as i64;
+        Some(self.position.x - rem..self.position.x + rem + 1)
     }
 }
 
 pub fn part1(input: &str, y: i64) -> i64 {
     let sensors: Vec<Sensor> = input.lines().map(Sensor::read).collect();
-    let mut bounds = Vec::new();
-    for sensor in sensors {
-        let dy = sensor.position.y.abs_diff(y);
-        if sensor.cell_radius < dy {
-            continue;
-        }
-        let rem = (sensor.cell_radius - dy) as i64;
-        bounds.push(IntervalBound::open(sensor.position.x - rem));
-        bounds.push(IntervalBound::close(sensor.position.x + rem));
-    }
-    bounds.sort();
-
-    let mut count = 0;
-    let mut depth = 0u64;
-    let mut first_open = None;
-    for bound in bounds {
-        match bound.kind {
-            BoundType::Open => {
-                if depth == 0 {
-                    first_open = Some(bound.position);
-                }
-                depth += 1;
-            }
-            BoundType::Close => {
-                depth -= 1;
-                if depth == 0 {
-                    count += bound.position - first_open.unwrap();
-                    first_open = None;
-                }
-            }
-        };
-    }
-    count
+    let covered: IntervalSet<i64> = sensors.iter().filter_map(|s| s.covered(y)).collect();
+    // cells with a beacon do not count
+    let beacons: HashSet<i64> = sensors
+        .iter()
+        .filter(|s| s.beacon.y == y && covered.contains(s.beacon.x))
+        .map(|s| s.beacon.x)
+        .collect();
+    covered.len() - beacons.len() as i64
 }
 
 pub fn part2(input: &str, size: i64) -> i64 {
     let sensors: Vec<Sensor> = input.lines().map(Sensor::read).collect();
-
+    let area = IntervalSet::from(0..size);
     for y in 0..size {
-        let mut bounds = Vec::new();
-        for sensor in &sensors {
-            let dy = sensor.position.y.abs_diff(y);
-            if sensor.cell_radius < dy {
-                continue;
-            }
-            let rem = (sensor.cell_radius - dy) as i64;
-            bounds.push((sensor.position.x - rem, sensor.position.x + rem));
-        }
-        bounds.sort();
-
-        let mut cover_stop = 0;
-        for (start, end) in bounds {
-            if start > cover_stop {
-                let c = Coord { x: cover_stop, y };
-                return c.tuning_frequency();
-            }
-            if end > cover_stop {
-                cover_stop = end + 1;
-            }
-            if cover_stop >= size {
-                break;
-            }
+        let covered: IntervalSet<i64> = sensors.iter().filter_map(|s| s.covered(y)).collect();
+        if let Some(x) = area.difference(&covered).min() {
+            return Coord { x, y }.tuning_frequency();
         }
     }
     unreachable!();
 }
 
+pub struct Day15;
+
+impl Solution for Day15 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 15;
+    const PART1_PARAMETERS: &'static [Parameter] = &[Parameter::new::<i64>("y", "2000000")];
+    const PART2_PARAMETERS: &'static [Parameter] = &[Parameter::new::<i64>("size", "4000000")];
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input, params.get("y")))
+    }
+
+    fn part2(input: &Self::Input<'_>, params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input, params.get("size"))))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
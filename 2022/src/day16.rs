@@ -2,23 +2,28 @@ use regex::Regex;
 use std::collections::{HashMap, VecDeque};
 use std::fmt;
 
+use aoc_common::dot::Dot;
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 #[derive(Clone, Copy, Eq, Ord, Hash, PartialEq, PartialOrd)]
 struct ValveID(u32);
 
 impl ValveID {
-    fn from(s: &str) -> Self {
-        fn parse_char(c: char) -> u32 {
+    const START: ValveID = ValveID(0); // AA
+
+    fn parse(s: &str) -> Option<Self> {
+        fn parse_char(c: u8) -> Option<u32> {
             match c {
-                'A'..='Z' => (c as u32) - ('A' as u32),
-                _ => unreachable!(),
+                b'A'..=b'Z' => Some((c - b'A') as u32),
+                _ => None,
             }
         }
 
-        assert_eq!(s.len(), 2);
-        let mut chars = s.chars();
-        let a = parse_char(chars.next().unwrap());
-        let b = parse_char(chars.next().unwrap());
-        ValveID(a * 26 + b)
+        match s.as_bytes() {
+            &[a, b] => Some(ValveID(parse_char(a)? * 26 + parse_char(b)?)),
+            _ => None,
+        }
     }
 }
 
@@ -39,7 +44,7 @@ struct Valve {
     neighbors: Vec<ValveID>,
 }
 
-struct Cave {
+pub struct Cave {
     valves: HashMap<ValveID, Valve>,
 }
 
@@ -51,15 +56,23 @@ struct State {
 }
 
 impl Cave {
-    fn read(input: &str) -> Self {
+    fn read(input: &str) -> Result<Self, ParseError> {
+        const EXPECTED: &str = "\"Valve XX has flow rate=N; tunnels lead to valves YY, ZZ\"";
         let mut tunnels = HashMap::new();
-        let r = Regex::new(r"Valve (\S*) has flow rate=(\d+); tunnels? leads? to valves? (.*)")
+        let mut lines = HashMap::new();
+        let r = Regex::new(r"^Valve (\S*) has flow rate=(\d+); tunnels? leads? to valves? (.*)$")
             .unwrap();
-        for line in input.lines() {
-            let caps = r.captures(line).unwrap();
-            let id = ValveID::from(&caps[1]);
-            let flow_rate = caps[2].parse().unwrap();
-            let neighbors = caps[3].split(", ").map(ValveID::from).collect();
+        for (i, line) in input.lines().enumerate() {
+            let error = || ParseError::new(i + 1, line, EXPECTED);
+            let caps = r.captures(line).ok_or_else(error)?;
+            let id = ValveID::parse(&caps[1]).ok_or_else(error)?;
+            let flow_rate = caps[2].parse().map_err(|_| error())?;
+            let neighbors = caps[3]
+                .split(", ")
+                .map(ValveID::parse)
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(error)?;
+            lines.insert(id, (i + 1, line));
             tunnels.insert(
                 id,
                 Valve {
@@ -69,7 +82,21 @@ impl Cave {
                 },
             );
         }
-        Cave { valves: tunnels }
+
+        // make sure that the tunnels do not lead to unknown valves
+        for valve in tunnels.values() {
+            if let Some(neighbor) = valve.neighbors.iter().find(|id| !tunnels.contains_key(id)) {
+                let (line, text) = lines[&valve.id];
+                let expected = format!("a tunnel to a known valve instead of {neighbor}");
+                return Err(ParseError::new(line, text, expected));
+            }
+        }
+        if !tunnels.contains_key(&ValveID::START) {
+            let expected = format!("a description of valve {}", ValveID::START);
+            return Err(ParseError::new(input.lines().count() + 1, "", expected));
+        }
+
+        Ok(Cave { valves: tunnels })
     }
 
     fn valve_distances(&self) -> HashMap<(ValveID, ValveID), u32> {
@@ -98,6 +125,29 @@ impl Cave {
         distances
     }
 
+    /// Valves with a positive flow rate are drawn with a double circle
+    pub fn to_dot(&self) -> String {
+        let mut ids: Vec<&ValveID> = self.valves.keys().collect();
+        ids.sort();
+        let mut dot = Dot::graph();
+        for id in ids {
+            let valve = &self.valves[id];
+            let name = id.to_string();
+            let label = format!("{name}\nrate={}", valve.flow_rate);
+            let shape = if valve.flow_rate > 0 {
+                "doublecircle"
+            } else {
+                "circle"
+            };
+            dot.node(&name, &[("label", &label), ("shape", shape)]);
+            // tunnels go both ways, only draw them once
+            for neighbor in valve.neighbors.iter().filter(|&neighbor| neighbor > id) {
+                dot.edge(&name, &neighbor.to_string(), &[]);
+            }
+        }
+        dot.to_string()
+    }
+
     fn end_states(&self, timeout: u32) -> HashMap<State, u32> {
         // only consider the valves with a positive flow rate
         let valves: HashMap<&ValveID, &Valve> = self
@@ -114,7 +164,7 @@ impl Cave {
         q.push_back((
             State {
                 open_valves: 0,
-                location: ValveID::from("AA"),
+                location: ValveID::START,
                 remaining_time: timeout,
             },
             0,
@@ -161,14 +211,12 @@ impl Cave {
     }
 }
 
-pub fn part1(input: &str) -> u32 {
-    let cave = Cave::read(input);
+fn max_pressure(cave: &Cave) -> u32 {
     let end_states = cave.end_states(30);
     *end_states.values().max().unwrap()
 }
 
-pub fn part2(input: &str) -> u32 {
-    let cave = Cave::read(input);
+fn max_pressure_with_elephant(cave: &Cave) -> u32 {
     let end_states = cave.end_states(26);
 
     let mut best = 0;
@@ -182,6 +230,35 @@ pub fn part2(input: &str) -> u32 {
     best
 }
 
+pub fn part1(input: &str) -> u32 {
+    max_pressure(&Cave::read(input).unwrap())
+}
+
+pub fn part2(input: &str) -> u32 {
+    max_pressure_with_elephant(&Cave::read(input).unwrap())
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 16;
+
+    type Input<'a> = Cave;
+
+    fn parse(input: &str) -> Result<Cave, ParseError> {
+        Cave::read(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl fmt::Display> {
+        Ok(max_pressure(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl fmt::Display>> {
+        Some(Ok(max_pressure_with_elephant(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -200,4 +277,33 @@ mod tests {
         assert_eq!(part2(EXAMPLE), 1707);
         assert_eq!(part2(INPUT), 2469);
     }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = Cave::read(EXAMPLE).unwrap().to_dot();
+        assert!(dot.starts_with("graph {\n    \"AA\" [label=\"AA\\nrate=0\", shape=\"circle\"];\n"));
+        assert!(dot.contains("    \"BB\" [label=\"BB\\nrate=13\", shape=\"doublecircle\"];\n"));
+        assert!(dot.contains("    \"AA\" -- \"DD\";\n"));
+        assert!(!dot.contains("    \"DD\" -- \"AA\";\n"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let input = "Valve AA has flow rate=0; tunnels lead to valves BB
+Valve BB has flow rate=x; tunnel leads to valve AA
+";
+        let error = Cave::read(input).err().unwrap();
+        assert_eq!(error.line, 2);
+        assert_eq!(
+            error.text,
+            "Valve BB has flow rate=x; tunnel leads to valve AA"
+        );
+
+        let input = "Valve AA has flow rate=0; tunnels lead to valves BB, CC
+Valve BB has flow rate=3; tunnel leads to valve AA
+";
+        let error = Cave::read(input).err().unwrap();
+        assert_eq!(error.line, 1);
+        assert_eq!(error.expected, "a tunnel to a known valve instead of CC");
+    }
 }
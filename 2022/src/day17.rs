@@ -1,4 +1,8 @@
-use std::collections::HashMap;
+use std::fmt::Display;
+
+use aoc_common::cycle::find_cycle;
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 const WIDTH: i64 = 7;
 const MARGIN_LEFT: i64 = 2;
@@ -31,7 +35,71 @@ impl Rock {
     }
 }
 
-fn measure_tower(input: &str, iterations: i64) -> i64 {
+#[derive(Clone)]
+struct Tower {
+    /// Bit masks of the rows near the top, starting from `occupied_bottom`
+    occupied: Vec<i64>,
+    occupied_bottom: i64,
+    top: i64,
+    current_rock: usize,
+    current_jet: usize,
+}
+
+impl Tower {
+    fn new() -> Self {
+        let occupied = vec![0; 50];
+        let occupied_bottom = MARGIN_BOTTOM + TALLEST_ROCK - (occupied.len() as i64);
+        Tower {
+            occupied,
+            occupied_bottom,
+            top: 0,
+            current_rock: 0,
+            current_jet: 0,
+        }
+    }
+
+    fn drop_rock(&mut self, rocks: &[Rock], jets: &[i64]) {
+        let occupied = &mut self.occupied;
+        let mut rock = rocks[self.current_rock].shift(MARGIN_LEFT, self.top + MARGIN_BOTTOM);
+        self.current_rock = (self.current_rock + 1) % rocks.len();
+
+        loop {
+            let jet = jets[self.current_jet];
+            self.current_jet = (self.current_jet + 1) % jets.len();
+
+            let next_rock = rock.shift(jet, 0);
+            if next_rock.can_be_there(occupied, self.occupied_bottom) {
+                rock = next_rock;
+            }
+
+            let next_rock = rock.shift(0, -1);
+            if !next_rock.can_be_there(occupied, self.occupied_bottom) {
+                break;
+            }
+            rock = next_rock;
+        }
+
+        for (x, y) in rock.parts {
+            if y >= self.top {
+                self.top = y + 1;
+            }
+            occupied[(y - self.occupied_bottom) as usize] |= 1 << x;
+        }
+
+        let needed_shift = occupied
+            [((occupied.len() as i64 - TALLEST_ROCK - MARGIN_BOTTOM) as usize)..]
+            .iter()
+            .take_while(|&&x| x != 0)
+            .count();
+        for _ in 0..needed_shift {
+            occupied.remove(0);
+            occupied.push(0);
+        }
+        self.occupied_bottom += needed_shift as i64;
+    }
+}
+
+fn measure_tower(input: &str, iterations: usize) -> i64 {
     let jets: Vec<i64> = input
         .trim()
         .chars()
@@ -63,80 +131,50 @@ fn measure_tower(input: &str, iterations: i64) -> i64 {
         Rock::from(&[(0, 0), (0, 1), (1, 0), (1, 1)]),
     ];
 
-    // map state to (iteration, tower height)
-    let mut last_seen = HashMap::new();
-
-    let mut occupied = vec![0; 50];
-    let mut occupied_bottom = MARGIN_BOTTOM + TALLEST_ROCK - (occupied.len() as i64);
-    let mut top = 0;
-    let mut current_rock = 0;
-    let mut current_jet = 0;
+    let step = |tower: &Tower| {
+        let mut tower = tower.clone();
+        tower.drop_rock(&rocks, &jets);
+        tower
+    };
+    // the top of the tower determines where the next rocks stop
+    let key = |tower: &Tower| {
+        (
+            tower.occupied.clone(),
+            tower.current_rock,
+            tower.current_jet,
+        )
+    };
+    let cycle = find_cycle(Tower::new(), step, key);
+    cycle.extrapolate(iterations, |tower| tower.top)
+}
 
-    let mut iteration = 0;
-    let mut found_cycle = false;
-    while iteration < iterations {
-        let mut rock = rocks[current_rock].shift(MARGIN_LEFT, top + MARGIN_BOTTOM);
-        current_rock = (current_rock + 1) % rocks.len();
+pub fn part1(input: &str) -> i64 {
+    measure_tower(input, 2022)
+}
 
-        loop {
-            let jet = jets[current_jet];
-            current_jet = (current_jet + 1) % jets.len();
+pub fn part2(input: &str) -> i64 {
+    measure_tower(input, 1_000_000_000_000)
+}
 
-            let next_rock = rock.shift(jet, 0);
-            if next_rock.can_be_there(&occupied, occupied_bottom) {
-                rock = next_rock;
-            }
+pub struct Day17;
 
-            let next_rock = rock.shift(0, -1);
-            if !next_rock.can_be_there(&occupied, occupied_bottom) {
-                break;
-            }
-            rock = next_rock;
-        }
+impl Solution for Day17 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 17;
 
-        for (x, y) in rock.parts {
-            if y >= top {
-                top = y + 1;
-            }
-            occupied[(y - occupied_bottom) as usize] |= 1 << x;
-        }
+    type Input<'a> = &'a str;
 
-        let needed_shift = occupied
-            [((occupied.len() as i64 - TALLEST_ROCK - MARGIN_BOTTOM) as usize)..]
-            .iter()
-            .take_while(|&&x| x != 0)
-            .count();
-        for _ in 0..needed_shift {
-            occupied.remove(0);
-            occupied.push(0);
-        }
-        occupied_bottom += needed_shift as i64;
-
-        iteration += 1;
-
-        if !found_cycle {
-            if let Some((previous_iteration, previous_top)) = last_seen.get(&occupied) {
-                let cycle_length = iteration - previous_iteration;
-                let cycle_height = top - previous_top;
-                let remaining_cycles = (iterations - iteration) / cycle_length;
-                iteration += remaining_cycles * cycle_length;
-                top += remaining_cycles * cycle_height;
-                occupied_bottom += remaining_cycles * cycle_height;
-                found_cycle = true;
-            } else {
-                last_seen.insert(occupied.clone(), (iteration, top));
-            }
-        }
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
     }
-    top
-}
 
-pub fn part1(input: &str) -> i64 {
-    measure_tower(input, 2022)
-}
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
 
-pub fn part2(input: &str) -> i64 {
-    measure_tower(input, 1_000_000_000_000)
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
 }
 
 #[cfg(test)]
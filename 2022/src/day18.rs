@@ -1,4 +1,8 @@
 use std::collections::HashSet;
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 #[derive(Clone, Eq, Hash, PartialEq)]
 struct Cube([i32; 3]);
@@ -78,6 +82,27 @@ pub fn part2(input: &str) -> usize {
         .sum()
 }
 
+pub struct Day18;
+
+impl Solution for Day18 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 18;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,38 +1,49 @@
 // some optimization ideas from https://old.reddit.com/r/adventofcode/comments/zpihwi/2022_day_19_solutions/j1xy1ye/
 use std::collections::{HashSet, VecDeque};
+use std::fmt::Display;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
 use regex::Regex;
 
-fn index_of_material(s: &str) -> usize {
+fn index_of_material(s: &str) -> Option<usize> {
     match s {
-        "ore" => 0,
-        "clay" => 1,
-        "obsidian" => 2,
-        "geode" => 3,
-        _ => unreachable!(),
+        "ore" => Some(0),
+        "clay" => Some(1),
+        "obsidian" => Some(2),
+        "geode" => Some(3),
+        _ => None,
     }
 }
 
-struct Blueprint {
+pub struct Blueprint {
     costs: [[u32; 4]; 4],
 }
 
 impl Blueprint {
-    fn parse(s: &str) -> Self {
+    fn parse(line: usize, s: &str) -> Result<Self, ParseError> {
         let mut costs = [[0; 4]; 4];
+        let mut seen = [false; 4];
         let regex = Regex::new(r"Each (.*?) robot costs (.*?)\.").unwrap();
         // iterate over robot types
         for m in regex.captures_iter(s) {
-            let robot = index_of_material(&m[1]);
+            let robot = index_of_material(&m[1])
+                .ok_or_else(|| ParseError::new(line, &m[1], "a material"))?;
+            seen[robot] = true;
             // iterate over ingredients
             for x in m[2].split(" and ") {
-                let (amount, material) = x.split_once(' ').unwrap();
-                let amount: u32 = amount.parse().unwrap();
-                let material = index_of_material(material);
+                let error = || ParseError::new(line, x, "an amount and a material, like \"2 ore\"");
+                let (amount, material) = x.split_once(' ').ok_or_else(error)?;
+                let amount: u32 = amount.parse().map_err(|_| error())?;
+                let material = index_of_material(material).ok_or_else(error)?;
                 costs[robot][material] = amount;
             }
         }
-        Blueprint { costs }
+        if seen != [true; 4] {
+            let expected = "the costs of ore, clay, obsidian and geode robots";
+            return Err(ParseError::new(line, s, expected));
+        }
+        Ok(Blueprint { costs })
     }
 
     fn max_geodes(&self, timeout: u32) -> u32 {
@@ -117,22 +128,59 @@ impl Blueprint {
     }
 }
 
-pub fn part1(input: &str) -> u32 {
-    let blueprints = input.lines().map(Blueprint::parse);
+fn parse_blueprints(input: &str) -> Result<Vec<Blueprint>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| Blueprint::parse(i + 1, line))
+        .collect()
+}
+
+fn quality_levels(blueprints: &[Blueprint]) -> u32 {
     blueprints
+        .iter()
         .enumerate()
         .map(|(i, blueprint)| blueprint.max_geodes(24) * (i as u32 + 1))
         .sum()
 }
 
-pub fn part2(input: &str) -> u32 {
-    let blueprints = input.lines().map(Blueprint::parse);
+fn first_geodes(blueprints: &[Blueprint]) -> u32 {
     blueprints
+        .iter()
         .take(3)
         .map(|blueprint| blueprint.max_geodes(32))
         .product()
 }
 
+pub fn part1(input: &str) -> u32 {
+    quality_levels(&parse_blueprints(input).unwrap())
+}
+
+pub fn part2(input: &str) -> u32 {
+    first_geodes(&parse_blueprints(input).unwrap())
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 19;
+
+    type Input<'a> = Vec<Blueprint>;
+
+    fn parse(input: &str) -> Result<Vec<Blueprint>, ParseError> {
+        parse_blueprints(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(quality_levels(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(first_geodes(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,4 +199,21 @@ mod tests {
         assert_eq!(part2(EXAMPLE), 3472);
         assert_eq!(part2(INPUT), 18816);
     }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_blueprints("Blueprint 1: Each ore robot costs 4 ore.\n")
+            .err()
+            .unwrap();
+        assert_eq!(error.line, 1);
+
+        let line = "Blueprint 2: Each ore robot costs 4 ore. \
+            Each clay robot costs 2 ore. \
+            Each obsidian robot costs 3 ore and 14 clay. \
+            Each geode robot costs 2 ore and seven obsidian.";
+        let input = format!("{}\n{line}\n", EXAMPLE.lines().next().unwrap());
+        let error = parse_blueprints(&input).err().unwrap();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.text, "seven obsidian");
+    }
 }
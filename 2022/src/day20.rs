@@ -1,3 +1,8 @@
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 use crate::implicit_treap::ImplicitTreap;
 
 fn decrypt(input: &str, decryption_key: i64, iterations: usize) -> i64 {
@@ -38,6 +43,27 @@ pub fn part2(input: &str) -> i64 {
     decrypt(input, 811589153, 10)
 }
 
+pub struct Day20;
+
+impl Solution for Day20 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 20;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,8 @@
 use std::collections::HashMap;
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 enum Direction {
     Left,
@@ -184,6 +188,27 @@ pub fn part2(input: &str) -> i64 {
     Graph::from(input).solve()
 }
 
+pub struct Day21;
+
+impl Solution for Day21 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 21;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
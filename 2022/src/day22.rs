@@ -1,3 +1,7 @@
+use std::fmt::Display;
+
+use aoc_common::error::{ParseError, SolveError};
+use aoc_common::solution::{Answer, Parameters, Solution};
 use regex::Regex;
 
 #[derive(Clone, Copy)]
@@ -22,6 +26,7 @@ struct Board {
     tiles: Vec<Vec<Tile>>,
     rows: i64,
     cols: i64,
+    cube: Result<Cube, String>,
 }
 
 impl Board {
@@ -36,12 +41,201 @@ impl Board {
             row.resize(cols, Tile::None);
         }
         let cols = cols as i64;
-        Board { tiles, rows, cols }
+        let mut board = Board {
+            tiles,
+            rows,
+            cols,
+            cube: Err(String::new()),
+        };
+        board.cube = Cube::fold(&board);
+        board
     }
 
     fn tile_at(&self, state: &State) -> Tile {
         self.tiles[state.y as usize][state.x as usize]
     }
+
+    fn is_on_board(&self, x: i64, y: i64) -> bool {
+        (0..self.cols).contains(&x)
+            && (0..self.rows).contains(&y)
+            && !matches!(self.tiles[y as usize][x as usize], Tile::None)
+    }
+}
+
+type Vector = [i64; 3];
+
+fn dot(a: Vector, b: Vector) -> i64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+fn neg(a: Vector) -> Vector {
+    a.map(|a| -a)
+}
+
+/// Square of the board, once folded in 3D space
+#[derive(Clone, Copy, Debug)]
+struct Face {
+    /// Coordinates of the top-left tile on the board
+    x: i64,
+    y: i64,
+    /// Points outwards the cube
+    normal: Vector,
+    /// Direction of increasing x on the board
+    right: Vector,
+    /// Direction of increasing y on the board
+    down: Vector,
+}
+
+impl Face {
+    /// Fold the neighboring face in the given direction on the board
+    fn neighbor(&self, facing: Facing, size: i64) -> Face {
+        let &Face {
+            x,
+            y,
+            normal,
+            right,
+            down,
+        } = self;
+        match facing {
+            Facing::Right => Face {
+                x: x + size,
+                normal: right,
+                right: neg(normal),
+                ..*self
+            },
+            Facing::Left => Face {
+                x: x - size,
+                normal: neg(right),
+                right: normal,
+                ..*self
+            },
+            Facing::Down => Face {
+                y: y + size,
+                normal: down,
+                down: neg(normal),
+                ..*self
+            },
+            Facing::Up => Face {
+                y: y - size,
+                normal: neg(down),
+                down: normal,
+                ..*self
+            },
+        }
+    }
+
+    /// Direction in 3D space of the given facing on this face
+    fn direction(&self, facing: Facing) -> Vector {
+        match facing {
+            Facing::Right => self.right,
+            Facing::Down => self.down,
+            Facing::Left => neg(self.right),
+            Facing::Up => neg(self.down),
+        }
+    }
+}
+
+/// How the six squares of the board fold into a cube
+#[derive(Debug)]
+struct Cube {
+    size: i64,
+    faces: Vec<Face>,
+}
+
+impl Cube {
+    /// Works for any of the 11 nets of the cube, in any orientation
+    fn fold(board: &Board) -> Result<Self, String> {
+        let tiles = (0..board.rows)
+            .flat_map(|y| (0..board.cols).map(move |x| (x, y)))
+            .filter(|&(x, y)| board.is_on_board(x, y))
+            .count() as i64;
+        let size = (1..).find(|size| 6 * size * size >= tiles).unwrap();
+        if 6 * size * size != tiles {
+            return Err(format!("{tiles} tiles cannot form the 6 faces of a cube"));
+        }
+
+        // fold the faces one by one, starting from the first one
+        let mut faces: Vec<Face> = Vec::new();
+        let mut stack = Vec::new();
+        let mut first_x = (0..board.cols).step_by(size as usize);
+        if let Some(x) = first_x.find(|&x| board.is_on_board(x, 0)) {
+            stack.push(Face {
+                x,
+                y: 0,
+                normal: [0, 0, 1],
+                right: [1, 0, 0],
+                down: [0, 1, 0],
+            });
+        }
+        while let Some(face) = stack.pop() {
+            if faces
+                .iter()
+                .any(|other| (other.x, other.y) == (face.x, face.y))
+            {
+                continue;
+            }
+            if faces.iter().any(|other| other.normal == face.normal) {
+                return Err("two squares of the board fold onto the same face".to_string());
+            }
+            for facing in [Facing::Up, Facing::Right, Facing::Down, Facing::Left] {
+                let neighbor = face.neighbor(facing, size);
+                if board.is_on_board(neighbor.x, neighbor.y) {
+                    stack.push(neighbor);
+                }
+            }
+            faces.push(face);
+        }
+        if faces.len() != 6 {
+            return Err(format!("the board has {} connected squares", faces.len()));
+        }
+        Ok(Cube { size, faces })
+    }
+
+    fn face_at(&self, x: i64, y: i64) -> &Face {
+        self.faces
+            .iter()
+            .find(|face| {
+                (0..self.size).contains(&(x - face.x)) && (0..self.size).contains(&(y - face.y))
+            })
+            .unwrap()
+    }
+
+    /// Cross the edge of the cube in front of `state`
+    fn wrap(&self, state: &State) -> State {
+        let size = self.size;
+        let from = self.face_at(state.x, state.y);
+        let direction = from.direction(state.facing);
+        let to = self
+            .faces
+            .iter()
+            .find(|face| face.normal == direction)
+            .unwrap();
+
+        // center of the tile in 3D space, with the cube spanning from -size to size on each axis
+        let center = |face: &Face, dx: i64, dy: i64| -> Vector {
+            std::array::from_fn(|i| {
+                face.normal[i] * size
+                    + face.right[i] * (2 * dx + 1 - size)
+                    + face.down[i] * (2 * dy + 1 - size)
+            })
+        };
+        let here = center(from, state.x - from.x, state.y - from.y);
+        // the next tile is half a tile further in the current direction, then half a tile down
+        let there: Vector = std::array::from_fn(|i| here[i] + direction[i] - from.normal[i]);
+        let dx = (dot(there, to.right) + size - 1) / 2;
+        let dy = (dot(there, to.down) + size - 1) / 2;
+
+        let heading = neg(from.normal);
+        let facing = [Facing::Up, Facing::Right, Facing::Down, Facing::Left]
+            .into_iter()
+            .find(|&facing| to.direction(facing) == heading)
+            .unwrap();
+        State {
+            x: to.x + dx,
+            y: to.y + dy,
+            facing,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -102,12 +296,14 @@ impl State {
         next_state
     }
 
-    fn next_cube_example(&self, _board: &Board) -> State {
-        self.advance().wrap_cube_example()
-    }
-
-    fn next_cube_input(&self, _board: &Board) -> State {
-        self.advance().wrap_cube_input()
+    fn next_cube(&self, board: &Board) -> State {
+        let next_state = self.advance();
+        if board.is_on_board(next_state.x, next_state.y) {
+            return next_state;
+        }
+        // checked by `cube_password`
+        let cube = board.cube.as_ref().unwrap();
+        cube.wrap(self)
     }
 
     fn advance(&self) -> State {
@@ -143,303 +339,6 @@ impl State {
             facing: self.facing,
         }
     }
-
-    //         0123456789012356 x
-    //          /--------\
-    //          |        |
-    // y -1     |        A          -1 y
-    //    0     |      ....          0
-    //    1     |   ,-I....L-----\   1
-    //    2     |   |  ....      |   2
-    //    3     B   C  ....      |   3
-    //    4    ............      |   4
-    //    5    ............M-\   |   5
-    //    6 /-J............  |   |   6
-    //    7 |  ............  D   |   7
-    //    8 |   E   F  ........  |   8
-    //    9 |   |   |  ........N-/   9
-    //   10 |   |   \-K........     10
-    //   11 |   |      ........     11
-    //   12 |   |        G   H      12
-    //      |   |        |   |
-    //      |   \--------/   |
-    //      \----------------/
-    //         0123456789012345 x
-    fn wrap_cube_example(&self) -> State {
-        let &State { x, y, facing } = self;
-        match facing {
-            Facing::Up => {
-                if y == -1 {
-                    // A
-                    State {
-                        x: 11 - x,
-                        y: 4,
-                        facing: Facing::Down,
-                    }
-                } else if y == 3 && (0..4).contains(&x) {
-                    // B
-                    State {
-                        x: 11 - x,
-                        y: 0,
-                        facing: Facing::Down,
-                    }
-                } else if y == 3 && (4..8).contains(&x) {
-                    // C
-                    State {
-                        x: 8,
-                        y: x - 4,
-                        facing: Facing::Right,
-                    }
-                } else if y == 7 && (12..16).contains(&x) {
-                    // D
-                    State {
-                        x: 11,
-                        y: 19 - x,
-                        facing: Facing::Left,
-                    }
-                } else {
-                    self.clone()
-                }
-            }
-            Facing::Down => {
-                if y == 8 && (0..4).contains(&x) {
-                    // E
-                    State {
-                        x: 11 - x,
-                        y: 11,
-                        facing: Facing::Up,
-                    }
-                } else if y == 8 && (4..8).contains(&x) {
-                    // F
-                    State {
-                        x: 8,
-                        y: 15 - x,
-                        facing: Facing::Right,
-                    }
-                } else if y == 12 && (8..12).contains(&x) {
-                    // G
-                    State {
-                        x: 11 - x,
-                        y: 7,
-                        facing: Facing::Up,
-                    }
-                } else if y == 12 && (12..16).contains(&x) {
-                    // H
-                    State {
-                        x: 0,
-                        y: 19 - x,
-                        facing: Facing::Right,
-                    }
-                } else {
-                    self.clone()
-                }
-            }
-            Facing::Left => {
-                if x == 7 && (0..4).contains(&y) {
-                    // I
-                    State {
-                        x: y + 4,
-                        y: 4,
-                        facing: Facing::Down,
-                    }
-                } else if x == -1 {
-                    // J
-                    State {
-                        x: 19 - y,
-                        y: 11,
-                        facing: Facing::Up,
-                    }
-                } else if x == 7 && (8..12).contains(&y) {
-                    // K
-                    State {
-                        x: 15 - y,
-                        y: 7,
-                        facing: Facing::Up,
-                    }
-                } else {
-                    self.clone()
-                }
-            }
-            Facing::Right => {
-                if x == 12 && (0..4).contains(&y) {
-                    // L
-                    State {
-                        x: 15,
-                        y: 11 - y,
-                        facing: Facing::Left,
-                    }
-                } else if x == 12 && (4..8).contains(&y) {
-                    // M
-                    State {
-                        x: 19 - y,
-                        y: 8,
-                        facing: Facing::Down,
-                    }
-                } else if x == 16 {
-                    // N
-                    State {
-                        x: 11,
-                        y: 11 - y,
-                        facing: Facing::Left,
-                    }
-                } else {
-                    self.clone()
-                }
-            }
-        }
-    }
-
-    //           0123456789012356 x (in tens)
-    //      /-----------\    /-------\
-    //      |           |    |       |
-    // y -1 |           A    B       | -1 y
-    // (  0 |         ..........     |  0 (
-    // i  1 |         ..........     |  1 i
-    // n  2 | /------G..........K-\  |  2 n
-    //    3 | |       ..........  |  |  3
-    // t  4 | |       ..........  |  |  4 t
-    // e  5 | |       .....  D    |  |  5 e
-    // n  6 | |       .....  |    |  |  6 n
-    // s  7 | |    /-H.....L-/    |  |  7 s
-    // )  8 | |    |  .....       |  |  8 )
-    //    9 | |    C  .....       |  |  9
-    //   10 | |  ..........       |  | 10
-    //   11 | |  ..........       |  | 11
-    //   12 | \-I..........M------/  | 12
-    //   13 |    ..........          | 13
-    //   14 |    ..........          | 14
-    //   15 |    .....  E            | 15
-    //   16 |    .....  |            | 16
-    //   17 \---J.....N-/            | 17
-    //   18      .....               | 18
-    //   19      .....               | 19
-    //             F                 |
-    //             |                 |
-    //             \-----------------/
-    //           0123456789012345 x (in tens)
-    fn wrap_cube_input(&self) -> State {
-        let &State { x, y, facing } = self;
-        match facing {
-            Facing::Up => {
-                if y == -1 && (50..100).contains(&x) {
-                    // A
-                    State {
-                        x: 0,
-                        y: x + 100,
-                        facing: Facing::Right,
-                    }
-                } else if y == -1 && (100..150).contains(&x) {
-                    // B
-                    State {
-                        x: x - 100,
-                        y: 199,
-                        facing: Facing::Up,
-                    }
-                } else if y == 99 && (0..50).contains(&x) {
-                    // C
-                    State {
-                        x: 50,
-                        y: x + 50,
-                        facing: Facing::Right,
-                    }
-                } else {
-                    self.clone()
-                }
-            }
-            Facing::Down => {
-                if y == 50 && (100..150).contains(&x) {
-                    // D
-                    State {
-                        x: 99,
-                        y: x - 50,
-                        facing: Facing::Left,
-                    }
-                } else if y == 150 && (50..100).contains(&x) {
-                    // E
-                    State {
-                        x: 49,
-                        y: x + 100,
-                        facing: Facing::Left,
-                    }
-                } else if y == 200 && (0..50).contains(&x) {
-                    // F
-                    State {
-                        x: x + 100,
-                        y: 0,
-                        facing: Facing::Down,
-                    }
-                } else {
-                    self.clone()
-                }
-            }
-            Facing::Left => {
-                if x == 49 && (0..50).contains(&y) {
-                    // G
-                    State {
-                        x: 0,
-                        y: 149 - y,
-                        facing: Facing::Right,
-                    }
-                } else if x == 49 && (50..100).contains(&y) {
-                    // H
-                    State {
-                        x: y - 50,
-                        y: 100,
-                        facing: Facing::Down,
-                    }
-                } else if x == -1 && (100..150).contains(&y) {
-                    // I
-                    State {
-                        x: 50,
-                        y: 149 - y,
-                        facing: Facing::Right,
-                    }
-                } else if x == -1 && (150..200).contains(&y) {
-                    // J
-                    State {
-                        x: y - 100,
-                        y: 0,
-                        facing: Facing::Down,
-                    }
-                } else {
-                    self.clone()
-                }
-            }
-            Facing::Right => {
-                if x == 150 && (0..50).contains(&y) {
-                    // K
-                    State {
-                        x: 99,
-                        y: 149 - y,
-                        facing: Facing::Left,
-                    }
-                } else if x == 100 && (50..100).contains(&y) {
-                    // L
-                    State {
-                        x: y + 50,
-                        y: 49,
-                        facing: Facing::Up,
-                    }
-                } else if x == 100 && (100..150).contains(&y) {
-                    // M
-                    State {
-                        x: 149,
-                        y: 149 - y,
-                        facing: Facing::Left,
-                    }
-                } else if x == 50 && (150..200).contains(&y) {
-                    // N
-                    State {
-                        x: y - 100,
-                        y: 149,
-                        facing: Facing::Up,
-                    }
-                } else {
-                    self.clone()
-                }
-            }
-        }
-    }
 }
 
 enum Instruction {
@@ -522,12 +421,37 @@ pub fn part1(input: &str) -> i64 {
     follow_path(input, State::next_torus)
 }
 
+/// Password at the end of the path on the folded board, or an error if the board does not fold
+/// into a cube
+pub fn cube_password(input: &str) -> Result<i64, String> {
+    let (board, _) = input.split_once("\n\n").unwrap();
+    Board::from(board).cube?;
+    Ok(follow_path(input, State::next_cube))
+}
+
 pub fn part2(input: &str) -> i64 {
-    follow_path(input, State::next_cube_input)
+    cube_password(input).unwrap()
 }
 
-pub fn part2_example(input: &str) -> i64 {
-    follow_path(input, State::next_cube_example)
+pub struct Day22;
+
+impl Solution for Day22 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 22;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(cube_password(input).map_err(SolveError::from))
+    }
 }
 
 #[cfg(test)]
@@ -545,7 +469,32 @@ mod tests {
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2_example(EXAMPLE), 5031);
+        assert_eq!(part2(EXAMPLE), 5031);
         assert_eq!(part2(INPUT), 15410);
     }
+
+    #[test]
+    fn test_fold() {
+        let (board, _) = EXAMPLE.split_once("\n\n").unwrap();
+        assert_eq!(Board::from(board).cube.unwrap().size, 4);
+        let (board, _) = INPUT.split_once("\n\n").unwrap();
+        assert_eq!(Board::from(board).cube.unwrap().size, 50);
+
+        // going around the equator of the cube leads back to the start
+        let board = Board::from("  ..\n  ..\n........\n........\n  ..\n  ..\n");
+        let mut state = State {
+            x: 0,
+            y: 2,
+            facing: Facing::Right,
+        };
+        for _ in 0..8 {
+            state = state.next_cube(&board);
+        }
+        assert_eq!((state.x, state.y, state.facing), (0, 2, Facing::Right));
+
+        let error = Board::from("......\n").cube.unwrap_err();
+        assert_eq!(error, "two squares of the board fold onto the same face");
+        let error = Board::from("..\n\n....\n").cube.unwrap_err();
+        assert_eq!(error, "the board has 2 connected squares");
+    }
 }
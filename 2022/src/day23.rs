@@ -1,4 +1,10 @@
 use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use aoc_common::animation::Animation;
+use aoc_common::error::ParseError;
+use aoc_common::grid::Grid;
+use aoc_common::solution::{Answer, Parameter, Parameters, Solution};
 
 struct Elves {
     elves: HashSet<(i64, i64)>,
@@ -22,21 +28,20 @@ impl Elves {
         Elves { elves, round: 0 }
     }
 
-    #[allow(dead_code)]
-    fn display(&self) {
+    /// Smallest rectangle containing all the elves
+    fn frame(&self) -> Grid<bool> {
         let elves = &self.elves;
         let min_i = elves.iter().map(|&(i, _)| i).min().unwrap();
         let max_i = elves.iter().map(|&(i, _)| i).max().unwrap();
         let min_j = elves.iter().map(|&(_, j)| j).min().unwrap();
         let max_j = elves.iter().map(|&(_, j)| j).max().unwrap();
-        for i in min_i..=max_i {
-            println!(
-                "{}",
-                (min_j..=max_j)
-                    .map(|j| if elves.contains(&(i, j)) { '#' } else { '.' })
-                    .collect::<String>()
-            );
+        let rows = (max_i - min_i + 1) as usize;
+        let cols = (max_j - min_j + 1) as usize;
+        let mut frame = Grid::new(rows, cols, false);
+        for &(i, j) in elves {
+            frame[((i - min_i) as usize, (j - min_j) as usize)] = true;
         }
+        frame
     }
 
     fn count_empty(&self) -> usize {
@@ -114,6 +119,40 @@ pub fn part2(input: &str) -> usize {
     elves.round + 1
 }
 
+/// Elves at the start and after each round, until none of them moves or after `max_rounds`
+pub fn animate(input: &str, max_rounds: usize) -> Animation {
+    let mut animation = Animation::new().with_color('#', [0, 192, 0]);
+    let mut elves = Elves::from(input);
+    animation.push("Initial State", &elves.frame());
+    while elves.round < max_rounds && elves.round() {
+        let caption = format!("End of Round {}", elves.round);
+        animation.push(caption, &elves.frame());
+    }
+    animation
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 23;
+    const PART1_PARAMETERS: &'static [Parameter] = &[Parameter::new::<usize>("count", "10")];
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input, params.get("count")))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,4 +171,13 @@ mod tests {
         assert_eq!(part2(EXAMPLE), 20);
         assert_eq!(part2(INPUT), 999);
     }
+
+    #[test]
+    fn test_animate() {
+        let animation = animate(EXAMPLE, 10);
+        assert_eq!(animation.len(), 11);
+        let last = animation.frame(10);
+        assert_eq!(last.iter().filter(|&(_, &c)| c == b'.').count(), 110);
+        assert_eq!(animate(EXAMPLE, usize::MAX).len(), 20);
+    }
 }
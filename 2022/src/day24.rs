@@ -1,4 +1,9 @@
-use std::collections::{BinaryHeap, HashSet};
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::search::astar;
+use aoc_common::solution::{Answer, Parameters, Solution};
+use aoc_common::trace;
 
 struct Blizzard {
     i: i32,
@@ -59,26 +64,11 @@ impl Map {
     }
 
     fn find_path(&self, start_time: i32, start: (i32, i32), goal: (i32, i32)) -> Option<i32> {
-        let (si, sj) = start;
         let (gi, gj) = goal;
-        let mut q = BinaryHeap::new();
-        q.push((0, (start_time, si, sj)));
-        let mut visited = HashSet::new();
-        while let Some((_, state)) = q.pop() {
-            if visited.contains(&state) {
-                continue;
-            }
-            visited.insert(state);
-            let (time, i, j) = state;
-            // println!("{time}: {i} {j}");
-            if i == gi && j == gj {
-                return Some(time);
-            }
+        let successors = |&(time, i, j): &(i32, i32, i32)| {
+            let mut next = Vec::new();
             if !self.blizzard_at(time + 1, i, j) {
-                q.push((
-                    -(i.abs_diff(gi) as i32 + j.abs_diff(gj) as i32 + time),
-                    (time + 1, i, j),
-                ));
+                next.push(((time + 1, i, j), 1));
             }
             for (ni, nj) in [(i - 1, j), (i, j - 1), (i, j + 1), (i + 1, j)] {
                 if !((0..self.rows).contains(&ni) && (0..self.cols).contains(&nj)) {
@@ -87,13 +77,19 @@ impl Map {
                 if self.blizzard_at(time + 1, ni, nj) {
                     continue;
                 }
-                q.push((
-                    -(ni.abs_diff(gi) as i32 + nj.abs_diff(gj) as i32 + time),
-                    (time + 1, ni, nj),
-                ));
+                next.push(((time + 1, ni, nj), 1));
             }
-        }
-        None
+            next
+        };
+        let heuristic = |&(_, i, j): &(i32, i32, i32)| (i.abs_diff(gi) + j.abs_diff(gj)) as i32;
+        let is_goal = |&(_, i, j): &(i32, i32, i32)| (i, j) == goal;
+        let search = astar(
+            [(start_time, start.0, start.1)],
+            successors,
+            heuristic,
+            is_goal,
+        );
+        search.goal().map(|&(time, _, _)| time)
     }
 }
 
@@ -115,10 +111,31 @@ pub fn part2(input: &str) -> i32 {
         .find_path(b, (-1, 0), (map.rows - 1, map.cols - 1))
         .unwrap()
         + 1;
-    println!("{a} {b} {c}");
+    trace!("reached the end at {a}, the start at {b}, the end again at {c}");
     c
 }
 
+pub struct Day24;
+
+impl Solution for Day24 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 24;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
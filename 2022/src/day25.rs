@@ -1,3 +1,8 @@
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 fn from_snafu_digit(c: char) -> i64 {
     match c {
         '2' => 2,
@@ -52,6 +57,23 @@ pub fn part1(input: &str) -> String {
     to_snafu_number(sum)
 }
 
+pub struct Day25;
+
+impl Solution for Day25 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 25;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,8 @@
 use std::collections::HashSet;
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 fn char_priority(c: char) -> u32 {
     match c {
@@ -38,6 +42,27 @@ pub fn part2(input: &str) -> u32 {
     total_priority
 }
 
+pub struct Day3;
+
+impl Solution for Day3 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 3;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,8 @@
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 struct Range {
     start: i32,
     end: i32,
@@ -47,6 +52,27 @@ pub fn part2(input: &str) -> i32 {
     count_relations(input, Range::overlaps)
 }
 
+pub struct Day4;
+
+impl Solution for Day4 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 4;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
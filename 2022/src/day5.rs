@@ -1,3 +1,8 @@
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 struct Map {
     stacks: Vec<Vec<char>>,
 }
@@ -20,16 +25,6 @@ impl Map {
         Map { stacks }
     }
 
-    #[allow(dead_code)]
-    fn display(self) {
-        for stack in &self.stacks {
-            for item in stack {
-                print!("{} ", item);
-            }
-            println!();
-        }
-    }
-
     fn code(self) -> String {
         self.stacks
             .iter()
@@ -87,6 +82,27 @@ pub fn part2(input: &str) -> String {
     move_creates(input, false)
 }
 
+pub struct Day5;
+
+impl Solution for Day5 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 5;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
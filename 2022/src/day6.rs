@@ -1,7 +1,11 @@
 use std::cmp::Eq;
 use std::collections::HashMap;
+use std::fmt::Display;
 use std::hash::Hash;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 struct MultiSet<T> {
     item_counts: HashMap<T, usize>,
 }
@@ -56,6 +60,27 @@ pub fn part2(input: &str) -> usize {
     marker_position(input, 14)
 }
 
+pub struct Day6;
+
+impl Solution for Day6 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 6;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
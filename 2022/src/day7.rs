@@ -1,7 +1,11 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::fmt::Display;
 use std::rc::Rc;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 #[derive(Debug)]
 struct FileNode {
     _name: String,
@@ -176,6 +180,27 @@ pub fn part2(input: &str) -> u32 {
     fs.smallest_above(missing_space).unwrap()
 }
 
+pub struct Day7;
+
+impl Solution for Day7 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 7;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
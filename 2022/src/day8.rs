@@ -1,30 +1,28 @@
 use std::collections::HashSet;
+use std::fmt::Display;
 
-fn read_grid(input: &str) -> Vec<Vec<i32>> {
-    input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).unwrap() as i32)
-                .collect()
-        })
-        .collect()
+use aoc_common::error::ParseError;
+use aoc_common::grid::Grid;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
+fn read_grid(input: &str) -> Result<Grid<i32>, ParseError> {
+    Grid::parse_with(input, "a digit", |c| {
+        (c as char).to_digit(10).map(|d| d as i32)
+    })
 }
 
-pub fn part1(input: &str) -> usize {
-    let grid = read_grid(input);
-    let rows = grid.len();
-    let cols = grid[0].len();
+fn visible_trees(grid: &Grid<i32>) -> usize {
+    let rows = grid.rows();
+    let cols = grid.cols();
 
     let mut visible_trees = HashSet::new();
 
     // horizontal
-    #[allow(clippy::needless_range_loop)]
     for i in 0..rows {
         // left-to-right
         let mut current_max_height = -1;
         for j in 0..cols {
-            let tree = grid[i][j];
+            let tree = grid[(i, j)];
             if tree > current_max_height {
                 visible_trees.insert((i, j));
                 current_max_height = tree;
@@ -33,7 +31,7 @@ pub fn part1(input: &str) -> usize {
         // right-to-left
         current_max_height = -1;
         for j in (0..cols).rev() {
-            let tree = grid[i][j];
+            let tree = grid[(i, j)];
             if tree > current_max_height {
                 visible_trees.insert((i, j));
                 current_max_height = tree;
@@ -45,9 +43,8 @@ pub fn part1(input: &str) -> usize {
     for j in 0..cols {
         // top-down
         let mut current_max_height = -1;
-        #[allow(clippy::needless_range_loop)]
         for i in 0..rows {
-            let tree = grid[i][j];
+            let tree = grid[(i, j)];
             if tree > current_max_height {
                 visible_trees.insert((i, j));
                 current_max_height = tree;
@@ -56,7 +53,7 @@ pub fn part1(input: &str) -> usize {
         // bottom-up
         current_max_height = -1;
         for i in (0..rows).rev() {
-            let tree = grid[i][j];
+            let tree = grid[(i, j)];
             if tree > current_max_height {
                 visible_trees.insert((i, j));
                 current_max_height = tree;
@@ -67,6 +64,10 @@ pub fn part1(input: &str) -> usize {
     visible_trees.len()
 }
 
+pub fn part1(input: &str) -> usize {
+    visible_trees(&read_grid(input).unwrap())
+}
+
 struct TakeWhileInclusive<I, P> {
     iter: I,
     flag: bool,
@@ -111,20 +112,19 @@ where
     TakeWhileInclusive::new(iter, predicate)
 }
 
-pub fn part2(input: &str) -> usize {
-    let grid = read_grid(input);
-    let rows = grid.len();
-    let cols = grid[0].len();
+fn best_scenic_score(grid: &Grid<i32>) -> usize {
+    let rows = grid.rows();
+    let cols = grid.cols();
 
     (0..rows)
         .map(|i| {
             (0..cols)
                 .map(|j| {
-                    let tree = grid[i][j];
-                    let up = take_while_inclusive((0..i).rev(), |a| grid[*a][j] < tree).count();
-                    let down = take_while_inclusive(i + 1..rows, |a| grid[*a][j] < tree).count();
-                    let left = take_while_inclusive((0..j).rev(), |b| grid[i][*b] < tree).count();
-                    let right = take_while_inclusive(j + 1..cols, |b| grid[i][*b] < tree).count();
+                    let tree = grid[(i, j)];
+                    let up = take_while_inclusive((0..i).rev(), |a| grid[(*a, j)] < tree).count();
+                    let down = take_while_inclusive(i + 1..rows, |a| grid[(*a, j)] < tree).count();
+                    let left = take_while_inclusive((0..j).rev(), |b| grid[(i, *b)] < tree).count();
+                    let right = take_while_inclusive(j + 1..cols, |b| grid[(i, *b)] < tree).count();
                     up * down * left * right
                 })
                 .max()
@@ -134,6 +134,31 @@ pub fn part2(input: &str) -> usize {
         .unwrap()
 }
 
+pub fn part2(input: &str) -> usize {
+    best_scenic_score(&read_grid(input).unwrap())
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 8;
+
+    type Input<'a> = Grid<i32>;
+
+    fn parse(input: &str) -> Result<Grid<i32>, ParseError> {
+        read_grid(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(visible_trees(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(best_scenic_score(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
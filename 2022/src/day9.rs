@@ -1,4 +1,8 @@
 use std::collections::HashSet;
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 fn count_tail_visits(input: &str, length: usize) -> usize {
     let mut visited_positions = HashSet::new();
@@ -47,6 +51,27 @@ pub fn part2(input: &str) -> usize {
     count_tail_visits(input, 9)
 }
 
+pub struct Day9;
+
+impl Solution for Day9 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 9;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,65 +1,181 @@
-use std::cmp::Ordering;
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{Add, Bound, RangeBounds};
 
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use slotmap::{new_key_type, Key, SlotMap};
 
 new_key_type! { pub struct NodeKey; }
-type Nodes<V> = SlotMap<NodeKey, Node<V>>;
-type Anchor = NodeKey;
+type Nodes<V, S> = SlotMap<NodeKey, Node<V, S>>;
+
+/// Summary of a sequence of values, computed from the summaries of its parts
+///
+/// `combine` must be associative, with `identity` as neutral element.
+pub trait Monoid<V> {
+    type Summary: Clone;
+    fn identity() -> Self::Summary;
+    fn summarize(value: &V) -> Self::Summary;
+    fn combine(a: &Self::Summary, b: &Self::Summary) -> Self::Summary;
+}
+
+/// No summary
+impl<V> Monoid<V> for () {
+    type Summary = ();
+    fn identity() {}
+    fn summarize(_value: &V) {}
+    fn combine(_a: &(), _b: &()) {}
+}
+
+pub struct Sum;
+
+impl<V: Copy + Add<Output = V> + Default> Monoid<V> for Sum {
+    type Summary = V;
+    fn identity() -> V {
+        V::default()
+    }
+    fn summarize(value: &V) -> V {
+        *value
+    }
+    fn combine(a: &V, b: &V) -> V {
+        *a + *b
+    }
+}
+
+/// `None` for an empty sequence
+pub struct Min;
+
+impl<V: Copy + Ord> Monoid<V> for Min {
+    type Summary = Option<V>;
+    fn identity() -> Option<V> {
+        None
+    }
+    fn summarize(value: &V) -> Option<V> {
+        Some(*value)
+    }
+    fn combine(a: &Option<V>, b: &Option<V>) -> Option<V> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(*a.min(b)),
+            _ => a.or(*b),
+        }
+    }
+}
+
+/// `None` for an empty sequence
+pub struct Max;
+
+impl<V: Copy + Ord> Monoid<V> for Max {
+    type Summary = Option<V>;
+    fn identity() -> Option<V> {
+        None
+    }
+    fn summarize(value: &V) -> Option<V> {
+        Some(*value)
+    }
+    fn combine(a: &Option<V>, b: &Option<V>) -> Option<V> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(*a.max(b)),
+            _ => a.or(*b),
+        }
+    }
+}
 
-struct Node<V> {
+#[derive(Clone)]
+struct Node<V, S> {
     value: V,
     priority: u64,
     count: usize,
-    children: [Anchor; 2],
+    // summaries of the subtree, in order and in reverse order
+    summary: S,
+    reversed_summary: S,
+    // the order of the subtree is reversed, but its children have not been swapped yet
+    reversed: bool,
+    children: [NodeKey; 2],
     parent: NodeKey,
 }
 
-impl<V> Node<V> {
-    fn new(value: V) -> Self {
-        Node {
-            value,
-            priority: rand::random(),
-            count: 1,
-            children: [NodeKey::null(); 2],
-            parent: NodeKey::null(),
-        }
-    }
+/// Sequence with `O(log n)` insertion, removal and lookup at any index
+///
+/// The nodes keep their key as long as they are in the treap, so `node_index` can be used to
+/// follow a value as it moves around. The nodes live in an arena owned by the treap, so moving
+/// them to another treap (`split_off`, `append`) takes linear time in the number of nodes moved,
+/// and gives them new keys; use `rotate_left`, `rotate_right` and `reverse` to rearrange a
+/// sequence in `O(log n)`.
+///
+/// The shape of the tree depends on random priorities, drawn from a generator seeded with
+/// `DEFAULT_SEED` unless another one is given with `with_rng`.
+pub struct ImplicitTreap<V, M: Monoid<V> = ()> {
+    nodes: Nodes<V, M::Summary>,
+    root: NodeKey,
+    rng: StdRng,
 }
 
-pub struct ImplicitTreap<V> {
-    nodes: Nodes<V>,
-    root: NodeKey,
+pub const DEFAULT_SEED: u64 = 42;
+
+/// Convert `range` to `start..end`, panicking if it is out of bounds
+fn bounds(range: impl RangeBounds<usize>, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end + 1,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    assert!(
+        start <= end && end <= len,
+        "range {start}..{end} out of bounds for length {len}"
+    );
+    (start, end)
 }
 
 impl<V> ImplicitTreap<V> {
+    /// Treap without summaries; use `default` to create one with a `Monoid`
     pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<V, M: Monoid<V>> ImplicitTreap<V, M> {
+    pub fn with_rng(rng: StdRng) -> Self {
         ImplicitTreap {
             nodes: Nodes::default(),
             root: NodeKey::null(),
+            rng,
         }
     }
 
     pub fn len(&self) -> usize {
-        match self.nodes.get(self.root) {
-            None => 0,
-            Some(node) => node.count,
-        }
+        self.count(self.root)
     }
 
     pub fn is_empty(&self) -> bool {
         self.nodes.get(self.root).is_none()
     }
 
+    fn count(&self, node_key: NodeKey) -> usize {
+        self.nodes.get(node_key).map_or(0, |node| node.count)
+    }
+
+    fn summary(&self, node_key: NodeKey, reversed: bool) -> M::Summary {
+        match self.nodes.get(node_key) {
+            None => M::identity(),
+            Some(node) if reversed => node.reversed_summary.clone(),
+            Some(node) => node.summary.clone(),
+        }
+    }
+
     #[cfg(test)]
     fn check(&self) {
         // returns the number of nodes in the subtree
-        fn aux<V>(
-            nodes: &Nodes<V>,
+        fn aux<V, M: Monoid<V>>(
+            treap: &ImplicitTreap<V, M>,
             node_key: NodeKey,
             parent: NodeKey,
             parent_priority: Option<u64>,
         ) -> usize {
-            match nodes.get(node_key) {
+            match treap.nodes.get(node_key) {
                 None => 0,
                 Some(node) => {
                     // check parent back reference
@@ -73,184 +189,199 @@ impl<V> ImplicitTreap<V> {
                     }
                     // recurse
                     let mut count = 0;
-                    count += aux(nodes, node.children[0], node_key, Some(node.priority));
+                    count += aux(treap, node.children[0], node_key, Some(node.priority));
                     count += 1;
-                    count += aux(nodes, node.children[1], node_key, Some(node.priority));
+                    count += aux(treap, node.children[1], node_key, Some(node.priority));
                     assert_eq!(count, node.count, "invalid node count for {node_key:?}");
                     count
                 }
             }
         }
-        aux(&self.nodes, self.root, NodeKey::null(), None);
+        aux(self, self.root, NodeKey::null(), None);
+        assert_eq!(self.len(), self.nodes.len(), "unreachable nodes");
     }
 
-    // attach new_node_key to its own parent, using old_node_key to know what child it was
-    fn set_parent_child(
-        &mut self,
-        parent_key: NodeKey,
-        old_node_key: NodeKey,
-        new_node_key: NodeKey,
-    ) {
-        match self.nodes.get_mut(parent_key) {
-            None => self.root = new_node_key,
-            Some(parent) => {
-                if parent.children[0] == old_node_key {
-                    parent.children[0] = new_node_key;
-                } else if parent.children[1] == old_node_key {
-                    parent.children[1] = new_node_key;
-                } else {
-                    unreachable!()
-                }
-            }
-        }
+    // recompute the count and summaries of node_key from its children
+    fn update(&mut self, node_key: NodeKey) {
+        let [left_key, right_key] = self.nodes[node_key].children;
+        let count = self.count(left_key) + 1 + self.count(right_key);
+        let value = M::summarize(&self.nodes[node_key].value);
+        let forward = M::combine(
+            &M::combine(&self.summary(left_key, false), &value),
+            &self.summary(right_key, false),
+        );
+        let backward = M::combine(
+            &M::combine(&self.summary(right_key, true), &value),
+            &self.summary(left_key, true),
+        );
+        let node = &mut self.nodes[node_key];
+        node.count = count;
+        // the children are still in the order before a pending reversal
+        (node.summary, node.reversed_summary) = if node.reversed {
+            (backward, forward)
+        } else {
+            (forward, backward)
+        };
     }
 
-    // move the dir-child of node_key to the top, while keeping the order
-    // returns the new top (dir-child)
-    fn rotate(&mut self, node_key: NodeKey, dir: usize) -> NodeKey {
-        let new_node_key = self.nodes[node_key].children[dir];
-        assert!(self.nodes[new_node_key].priority > self.nodes[node_key].priority);
-
-        let parent_key = self.nodes[node_key].parent;
+    // reverse the order of the subtree of node_key, without touching its descendants yet
+    fn toggle(&mut self, node_key: NodeKey) {
+        if let Some(node) = self.nodes.get_mut(node_key) {
+            node.reversed = !node.reversed;
+            std::mem::swap(&mut node.summary, &mut node.reversed_summary);
+        }
+    }
 
-        // detach node
-        self.nodes[node_key].parent = NodeKey::null();
+    // apply a pending reversal to the children of node_key
+    fn push_down(&mut self, node_key: NodeKey) {
+        let node = &mut self.nodes[node_key];
+        if !node.reversed {
+            return;
+        }
+        node.reversed = false;
+        node.children.swap(0, 1);
+        for child_key in node.children {
+            self.toggle(child_key);
+        }
+    }
 
-        // detach new_node from node
-        self.nodes[new_node_key].parent = NodeKey::null();
-        self.nodes[node_key].children[dir] = NodeKey::null();
-        self.nodes[node_key].count -= self.nodes[new_node_key].count;
+    fn set_child(&mut self, node_key: NodeKey, dir: usize, child_key: NodeKey) {
+        self.nodes[node_key].children[dir] = child_key;
+        if let Some(child) = self.nodes.get_mut(child_key) {
+            child.parent = node_key;
+        }
+    }
 
-        // move grand-child from new_node to node
-        let grand_child_key = self.nodes[new_node_key].children[1 - dir];
-        if let Some(grand_child) = self.nodes.get_mut(grand_child_key) {
-            grand_child.parent = node_key;
-            let grand_child_count = grand_child.count;
-            self.nodes[new_node_key].children[1 - dir] = NodeKey::null();
-            self.nodes[new_node_key].count -= grand_child_count;
-            self.nodes[node_key].children[dir] = grand_child_key;
-            self.nodes[node_key].count += grand_child_count;
+    // split the subtree of node_key into its first index values and the others
+    // both new subtrees are detached from any parent
+    fn split(&mut self, node_key: NodeKey, index: usize) -> (NodeKey, NodeKey) {
+        if self.nodes.get(node_key).is_none() {
+            return (NodeKey::null(), NodeKey::null());
         }
+        self.push_down(node_key);
+        let [left_key, right_key] = self.nodes[node_key].children;
+        let left_count = self.count(left_key);
+        let ret = if index <= left_count {
+            let (a, b) = self.split(left_key, index);
+            self.set_child(node_key, 0, b);
+            (a, node_key)
+        } else {
+            let (a, b) = self.split(right_key, index - left_count - 1);
+            self.set_child(node_key, 1, a);
+            (node_key, b)
+        };
+        self.update(node_key);
+        self.nodes[node_key].parent = NodeKey::null();
+        ret
+    }
 
-        // attach node to new_node
-        self.nodes[node_key].parent = new_node_key;
-        self.nodes[new_node_key].children[1 - dir] = node_key;
-        self.nodes[new_node_key].count += self.nodes[node_key].count;
+    // concatenate two detached subtrees, and return the root of the result
+    fn merge(&mut self, left_key: NodeKey, right_key: NodeKey) -> NodeKey {
+        let (Some(left), Some(right)) = (self.nodes.get(left_key), self.nodes.get(right_key))
+        else {
+            return if left_key.is_null() {
+                right_key
+            } else {
+                left_key
+            };
+        };
+        let ret = if left.priority > right.priority {
+            self.push_down(left_key);
+            let child_key = self.nodes[left_key].children[1];
+            let merged_key = self.merge(child_key, right_key);
+            self.set_child(left_key, 1, merged_key);
+            left_key
+        } else {
+            self.push_down(right_key);
+            let child_key = self.nodes[right_key].children[0];
+            let merged_key = self.merge(left_key, child_key);
+            self.set_child(right_key, 0, merged_key);
+            right_key
+        };
+        self.update(ret);
+        self.nodes[ret].parent = NodeKey::null();
+        ret
+    }
 
-        // attach new_node to node's parent
-        self.nodes[new_node_key].parent = parent_key;
-        self.set_parent_child(parent_key, node_key, new_node_key);
+    // split the whole treap into the values before start, the ones in start..end, and the others
+    fn split3(&mut self, start: usize, end: usize) -> (NodeKey, NodeKey, NodeKey) {
+        let (rest, right) = self.split(self.root, end);
+        let (left, middle) = self.split(rest, start);
+        self.root = NodeKey::null();
+        (left, middle, right)
+    }
 
-        new_node_key
+    fn merge3(&mut self, left: NodeKey, middle: NodeKey, right: NodeKey) {
+        let rest = self.merge(left, middle);
+        self.root = self.merge(rest, right);
     }
 
     pub fn insert(&mut self, index: usize, value: V) -> NodeKey {
-        // returns true when rebalancing might be needed
-        fn aux<V>(
-            treap: &mut ImplicitTreap<V>,
-            node_key: NodeKey,
-            mut index: usize,
-            new_node_key: NodeKey,
-        ) -> (NodeKey, bool) {
-            let nodes = &mut treap.nodes;
-            let current_index = nodes.get(node_key).map_or(0, |node| {
-                nodes.get(node.children[0]).map_or(0, |child| child.count)
-            });
-            if index == current_index {
-                if nodes.get(node_key).is_some() {
-                    let left_key = nodes[node_key].children[0];
-                    let parent_key = nodes[node_key].parent;
-                    nodes[new_node_key].parent = parent_key;
-                    nodes[new_node_key].count = nodes[node_key].count + 1;
-                    nodes[new_node_key].children[0] = left_key;
-                    nodes[new_node_key].children[1] = node_key;
-                    if let Some(left) = nodes.get_mut(left_key) {
-                        left.parent = new_node_key;
-                    }
-                    nodes[node_key].parent = new_node_key;
-                    nodes[node_key].children[0] = NodeKey::null();
-                    nodes[node_key].count = 1 + nodes
-                        .get(nodes[node_key].children[1])
-                        .map_or(0, |child| child.count);
-                    treap.set_parent_child(parent_key, node_key, new_node_key);
-                    (treap.bubble_down(new_node_key), true)
-                } else {
-                    (new_node_key, true)
-                }
-            } else {
-                let dir = if index < current_index {
-                    0
-                } else {
-                    // index > current_index
-                    index -= current_index + 1;
-                    1
-                };
-                let (new_child_key, should_rebalance) = aux(
-                    treap,
-                    treap.nodes[node_key].children[dir],
-                    index,
-                    new_node_key,
-                );
-                let nodes = &mut treap.nodes;
-                nodes[node_key].children[dir] = new_child_key;
-                nodes[new_child_key].parent = node_key;
-                nodes[node_key].count += 1;
-                if !should_rebalance {
-                    return (node_key, false);
-                }
-                let child_key = nodes[node_key].children[dir];
-                assert_eq!(child_key, new_child_key);
-                if nodes[child_key].priority > nodes[node_key].priority {
-                    // bubble up
-                    (treap.rotate(node_key, dir), true)
-                } else {
-                    (node_key, false)
-                }
-            }
-        }
-        let new_node_key = self.nodes.insert(Node::new(value));
-        (self.root, _) = aux(self, self.root, index, new_node_key);
+        assert!(
+            index <= self.len(),
+            "insertion index {index} out of bounds for length {}",
+            self.len()
+        );
+        let summary = M::summarize(&value);
+        let node = Node {
+            reversed_summary: summary.clone(),
+            summary,
+            value,
+            priority: self.rng.gen(),
+            count: 1,
+            reversed: false,
+            children: [NodeKey::null(); 2],
+            parent: NodeKey::null(),
+        };
+        let new_node_key = self.nodes.insert(node);
+        let (left, right) = self.split(self.root, index);
+        self.merge3(left, new_node_key, right);
         new_node_key
     }
 
     pub fn push(&mut self, value: V) -> NodeKey {
-        let index = self.nodes.get(self.root).map_or(0, |node| node.count);
-        self.insert(index, value)
+        self.insert(self.len(), value)
     }
 
-    pub fn find(&self, index: usize) -> NodeKey {
-        fn aux<V>(nodes: &Nodes<V>, node_key: NodeKey, index: usize) -> NodeKey {
-            match nodes.get(node_key) {
-                None => NodeKey::null(),
-                Some(node) => {
-                    let current_index = nodes.get(node.children[0]).map_or(0, |child| child.count);
-                    match index.cmp(&current_index) {
-                        Ordering::Equal => node_key,
-                        Ordering::Less => aux(nodes, node.children[0], index),
-                        Ordering::Greater => {
-                            aux(nodes, node.children[1], index - current_index - 1)
-                        }
-                    }
-                }
+    /// Key of the node at `index`, or a null key if it is out of bounds
+    pub fn find(&self, mut index: usize) -> NodeKey {
+        let mut node_key = self.root;
+        // whether the ancestors reverse the order of the current subtree
+        let mut reversed = false;
+        while let Some(node) = self.nodes.get(node_key) {
+            reversed ^= node.reversed;
+            let [first, second] = if reversed {
+                [node.children[1], node.children[0]]
+            } else {
+                node.children
+            };
+            let current_index = self.count(first);
+            if index < current_index {
+                node_key = first;
+            } else if index == current_index {
+                return node_key;
+            } else {
+                index -= current_index + 1;
+                node_key = second;
             }
         }
-        aux(&self.nodes, self.root, index)
+        NodeKey::null()
     }
 
     pub fn node_index(&self, mut node_key: NodeKey) -> usize {
+        // index within the subtree of node_key, taking the reversals in the subtree into account
         let mut node = &self.nodes[node_key];
-        let left_key = node.children[0];
-        // count left children
-        let mut ret = self.nodes.get(left_key).map_or(0, |left| left.count);
-        // count left uncles
+        let mut ret = self.count(node.children[0]);
+        if node.reversed {
+            ret = node.count - 1 - ret;
+        }
         while let Some(parent) = self.nodes.get(node.parent) {
             if node_key == parent.children[1] {
-                // was right-child, need to count…
-                let left_key = parent.children[0];
-                // …the parent's left siblings
-                ret += self.nodes.get(left_key).map_or(0, |left| left.count);
-                // …and the parent itself
-                ret += 1;
+                // skip the left subtree and the parent itself
+                ret += self.count(parent.children[0]) + 1;
+            }
+            if parent.reversed {
+                ret = parent.count - 1 - ret;
             }
             node_key = node.parent;
             node = parent;
@@ -258,166 +389,185 @@ impl<V> ImplicitTreap<V> {
         ret
     }
 
-    // remove the leftmost descendant of node_key
-    // preserve the invariants
-    fn leftmost(&mut self, node_key: NodeKey) -> NodeKey {
-        let left_key = self.nodes[node_key].children[0];
-        let left_left_key = self.nodes[left_key].children[0];
-        if self.nodes.get(left_left_key).is_some() {
-            let ret = self.leftmost(left_key);
-            self.nodes[node_key].count -= 1;
-            ret
-        } else {
-            let left_right_key = self.nodes[left_key].children[1];
-            self.nodes[node_key].count -= 1;
-            self.nodes[node_key].children[0] = left_right_key;
-            if let Some(left_right) = self.nodes.get_mut(left_right_key) {
-                left_right.parent = node_key;
-            }
-            left_key
-        }
+    pub fn get(&self, index: usize) -> Option<&V> {
+        self.nodes.get(self.find(index)).map(|node| &node.value)
     }
 
-    // move node_key to its proper position downwards
-    // return the key of the node which is at its position at the end
-    fn bubble_down(&mut self, node_key: NodeKey) -> NodeKey {
-        let node = &self.nodes[node_key];
-        let mut max_priority = node.priority;
-        let mut max_priority_dir = 2;
-        if let Some(child) = self.nodes.get(node.children[0]) {
-            if child.priority > max_priority {
-                max_priority = child.priority;
-                max_priority_dir = 0;
-            }
-        }
-        if let Some(child) = self.nodes.get(node.children[1]) {
-            if child.priority > max_priority {
-                // max_priority = child.priority;
-                max_priority_dir = 1;
-            }
+    /// Modify the value at `index` in place, keeping the summaries up to date
+    pub fn update_at(&mut self, index: usize, f: impl FnOnce(&mut V)) {
+        let mut node_key = self.find(index);
+        f(&mut self.nodes[node_key].value);
+        while !node_key.is_null() {
+            self.update(node_key);
+            node_key = self.nodes[node_key].parent;
         }
-        if max_priority_dir == 2 {
-            // nothing to do
-            return node_key;
-        }
-        // move max_priority_dir-child in the place of node_key
-        let ret = self.rotate(node_key, max_priority_dir);
-        self.bubble_down(node_key);
-        ret
     }
 
     pub fn remove_node(&mut self, node_key: NodeKey) -> Option<V> {
-        match self.nodes.remove(node_key) {
-            None => None,
-            Some(node) => {
-                let parent_key = node.parent;
-                // find replacement node, if any
-                let left_key = node.children[0];
-                let right_key = node.children[1];
-                match (self.nodes.get(left_key), self.nodes.get(right_key)) {
-                    (None, None) => {
-                        self.set_parent_child(parent_key, node_key, NodeKey::null());
-                    }
-                    (Some(_), None) => {
-                        self.nodes[node.children[0]].parent = node.parent;
-                        self.set_parent_child(parent_key, node_key, node.children[0]);
-                    }
-                    (None, Some(_)) => {
-                        self.nodes[node.children[1]].parent = node.parent;
-                        self.set_parent_child(parent_key, node_key, node.children[1]);
-                    }
-                    (Some(_), Some(right)) => {
-                        let right_left_key = right.children[0];
-                        match self.nodes.get(right_left_key) {
-                            None => {
-                                self.nodes[left_key].parent = right_key;
-                                let right = &mut self.nodes[right_key];
-                                right.parent = node.parent;
-                                right.children[0] = left_key;
-                                right.count = node.count - 1;
-                                self.set_parent_child(node.parent, node_key, right_key);
-                                self.bubble_down(right_key);
-                            }
-                            Some(_) => {
-                                let new_node_key = self.leftmost(right_key);
-                                let new_node = &mut self.nodes[new_node_key];
-                                new_node.count = node.count - 1;
-                                new_node.parent = node.parent;
-                                new_node.children[0] = left_key;
-                                new_node.children[1] = right_key;
-                                self.nodes[left_key].parent = new_node_key;
-                                self.nodes[right_key].parent = new_node_key;
-                                self.set_parent_child(node.parent, node_key, new_node_key);
-                                self.bubble_down(new_node_key);
-                            }
-                        }
-                    }
-                };
-                // update parents' node counts
-                let mut node_key = parent_key;
-                while let Some(node) = self.nodes.get_mut(node_key) {
-                    node.count -= 1;
-                    node_key = node.parent;
-                }
-                // return value of old node
-                Some(node.value)
-            }
-        }
+        self.nodes.get(node_key)?;
+        let index = self.node_index(node_key);
+        let (left, middle, right) = self.split3(index, index + 1);
+        assert_eq!(middle, node_key);
+        self.root = self.merge(left, right);
+        self.nodes.remove(node_key).map(|node| node.value)
     }
 
     pub fn remove_at(&mut self, index: usize) -> Option<V> {
         let node_key = self.find(index);
-        assert_eq!(self.node_index(node_key), index, "node_index");
         self.remove_node(node_key)
     }
 
     pub fn pop(&mut self) -> Option<V> {
-        if let Some(node) = self.nodes.get(self.root) {
-            self.remove_at(node.count - 1)
-        } else {
-            None
+        match self.len() {
+            0 => None,
+            len => self.remove_at(len - 1),
+        }
+    }
+
+    /// Reverse the order of the values in `range`
+    pub fn reverse(&mut self, range: impl RangeBounds<usize>) {
+        let (start, end) = bounds(range, self.len());
+        let (left, middle, right) = self.split3(start, end);
+        self.toggle(middle);
+        self.merge3(left, middle, right);
+    }
+
+    /// Rotate the sequence so that the value at `mid` becomes the first one
+    pub fn rotate_left(&mut self, mid: usize) {
+        assert!(
+            mid <= self.len(),
+            "rotation by {mid} for length {}",
+            self.len()
+        );
+        let (left, right) = self.split(self.root, mid);
+        self.root = self.merge(right, left);
+    }
+
+    /// Rotate the sequence so that the last `k` values come first
+    pub fn rotate_right(&mut self, k: usize) {
+        assert!(k <= self.len(), "rotation by {k} for length {}", self.len());
+        self.rotate_left(self.len() - k);
+    }
+
+    /// Summary of the values in `range`
+    pub fn aggregate(&self, range: impl RangeBounds<usize>) -> M::Summary {
+        // summary of the values in start..end of the subtree of node_key, in reverse order if the
+        // ancestors of node_key reverse it
+        fn aux<V, M: Monoid<V>>(
+            treap: &ImplicitTreap<V, M>,
+            node_key: NodeKey,
+            start: usize,
+            end: usize,
+            mut reversed: bool,
+        ) -> M::Summary {
+            let Some(node) = treap.nodes.get(node_key) else {
+                return M::identity();
+            };
+            if start >= end {
+                return M::identity();
+            }
+            if start == 0 && end == node.count {
+                return treap.summary(node_key, reversed);
+            }
+            reversed ^= node.reversed;
+            let [first, second] = if reversed {
+                [node.children[1], node.children[0]]
+            } else {
+                node.children
+            };
+            let index = treap.count(first);
+            let mut ret = aux(treap, first, start, end.min(index), reversed);
+            if (start..end).contains(&index) {
+                ret = M::combine(&ret, &M::summarize(&node.value));
+            }
+            let rest = aux(
+                treap,
+                second,
+                start.max(index + 1) - (index + 1),
+                end.max(index + 1) - (index + 1),
+                reversed,
+            );
+            M::combine(&ret, &rest)
         }
+        let (start, end) = bounds(range, self.len());
+        aux(self, self.root, start, end, false)
+    }
+
+    /// Move the values from `at` onwards to a new treap, in time linear in their number; their
+    /// node keys change
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len(), "split index {at} out of bounds");
+        let (left, right) = self.split(self.root, at);
+        self.root = left;
+        let mut other = Self::with_rng(StdRng::seed_from_u64(self.rng.gen()));
+        other.root = self.move_subtree(right, &mut other.nodes, NodeKey::null());
+        other
+    }
+
+    /// Move all the values of `other` to the end of this treap, in time linear in the length of
+    /// `other`; their node keys change
+    pub fn append(&mut self, mut other: Self) {
+        let moved = other.move_subtree(other.root, &mut self.nodes, NodeKey::null());
+        self.root = self.merge(self.root, moved);
+    }
+
+    // move the subtree of node_key to the arena nodes, below parent
+    // return the new key of its root
+    fn move_subtree(
+        &mut self,
+        node_key: NodeKey,
+        nodes: &mut Nodes<V, M::Summary>,
+        parent: NodeKey,
+    ) -> NodeKey {
+        let Some(mut node) = self.nodes.remove(node_key) else {
+            return NodeKey::null();
+        };
+        let children = node.children;
+        node.parent = parent;
+        let new_key = nodes.insert(node);
+        for (dir, child_key) in children.into_iter().enumerate() {
+            let new_child_key = self.move_subtree(child_key, nodes, new_key);
+            nodes[new_key].children[dir] = new_child_key;
+        }
+        new_key
     }
 }
 
-impl<V> Default for ImplicitTreap<V> {
+impl<V, M: Monoid<V>> Default for ImplicitTreap<V, M> {
     fn default() -> Self {
-        ImplicitTreap::new()
+        Self::with_rng(StdRng::seed_from_u64(DEFAULT_SEED))
     }
 }
 
-impl<V: std::fmt::Display> ImplicitTreap<V> {
-    pub fn print_vec(&self) {
-        fn aux<V: std::fmt::Display>(nodes: &Nodes<V>, node_key: NodeKey) {
-            match nodes.get(node_key) {
-                None => (),
-                Some(node) => {
-                    aux(nodes, node.children[0]);
-                    print!("{} [{:?}], ", node.value, node_key);
-                    aux(nodes, node.children[1])
-                }
-            }
+impl<V: Clone, M: Monoid<V>> Clone for ImplicitTreap<V, M> {
+    fn clone(&self) -> Self {
+        ImplicitTreap {
+            nodes: self.nodes.clone(),
+            root: self.root,
+            rng: self.rng.clone(),
         }
-        aux(&self.nodes, self.root);
-        println!();
     }
+}
 
-    pub fn print_tree(&self) {
-        fn aux<V: std::fmt::Display>(nodes: &Nodes<V>, node_key: NodeKey, depth: usize) {
-            let prefix = "    ".repeat(depth);
-            match nodes.get(node_key) {
-                None => println!("{}-", prefix),
-                Some(node) => {
-                    aux(nodes, node.children[1], depth + 1);
-                    println!(
-                        "{}- {} (priority={}, count={}) [{:?} <- {:?}]",
-                        prefix, node.value, node.priority, node.count, node.parent, node_key
-                    );
-                    aux(nodes, node.children[0], depth + 1);
-                }
-            }
+impl<V: Debug, M: Monoid<V>> Debug for ImplicitTreap<V, M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<V, M: Monoid<V>> FromIterator<V> for ImplicitTreap<V, M> {
+    fn from_iter<I: IntoIterator<Item = V>>(iter: I) -> Self {
+        let mut treap = Self::default();
+        treap.extend(iter);
+        treap
+    }
+}
+
+impl<V, M: Monoid<V>> Extend<V> for ImplicitTreap<V, M> {
+    fn extend<I: IntoIterator<Item = V>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
         }
-        aux(&self.nodes, self.root, 0);
     }
 }
 
@@ -427,71 +577,70 @@ enum ExplorationState {
     LeftYielded,
 }
 
-pub struct IterRef<'a, V> {
-    treap: &'a ImplicitTreap<V>,
-    stack: Vec<(ExplorationState, NodeKey)>,
+pub struct IterRef<'a, V, M: Monoid<V> = ()> {
+    treap: &'a ImplicitTreap<V, M>,
+    // whether the order of the subtree is reversed by the node or its ancestors
+    stack: Vec<(ExplorationState, NodeKey, bool)>,
 }
 
-impl<'a, V> IterRef<'a, V> {
-    fn new(treap: &'a ImplicitTreap<V>) -> Self {
-        if treap.nodes.get(treap.root).is_some() {
-            IterRef {
-                treap,
-                stack: vec![(ExplorationState::Unexplored, treap.root)],
-            }
-        } else {
-            IterRef {
-                treap,
-                stack: vec![],
-            }
+impl<'a, V, M: Monoid<V>> IterRef<'a, V, M> {
+    fn new(treap: &'a ImplicitTreap<V, M>) -> Self {
+        let mut stack = Vec::new();
+        if let Some(root) = treap.nodes.get(treap.root) {
+            stack.push((ExplorationState::Unexplored, treap.root, root.reversed));
+        }
+        IterRef { treap, stack }
+    }
+
+    fn push_child(&mut self, node_key: NodeKey, reversed: bool) {
+        if let Some(child) = self.treap.nodes.get(node_key) {
+            let reversed = reversed ^ child.reversed;
+            self.stack
+                .push((ExplorationState::Unexplored, node_key, reversed));
         }
     }
 }
 
-impl<'a, V> Iterator for IterRef<'a, V> {
+impl<'a, V, M: Monoid<V>> Iterator for IterRef<'a, V, M> {
     type Item = &'a V;
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some((state, node_key)) = self.stack.pop() {
-            match state {
-                ExplorationState::Unexplored => {
-                    self.stack.push((ExplorationState::LeftYielded, node_key));
-                    let node = &self.treap.nodes[node_key];
-                    if self.treap.nodes.get(node.children[0]).is_some() {
-                        self.stack
-                            .push((ExplorationState::Unexplored, node.children[0]));
-                    }
-                    self.next()
-                }
-                ExplorationState::LeftYielded => {
-                    let node = &self.treap.nodes[node_key];
-                    if self.treap.nodes.get(node.children[1]).is_some() {
-                        self.stack
-                            .push((ExplorationState::Unexplored, node.children[1]));
-                    }
-                    Some(&node.value)
-                }
-            }
+        let (state, node_key, reversed) = self.stack.pop()?;
+        let node = &self.treap.nodes[node_key];
+        let [first, second] = if reversed {
+            [node.children[1], node.children[0]]
         } else {
-            None
+            node.children
+        };
+        match state {
+            ExplorationState::Unexplored => {
+                self.stack
+                    .push((ExplorationState::LeftYielded, node_key, reversed));
+                self.push_child(first, reversed);
+                self.next()
+            }
+            ExplorationState::LeftYielded => {
+                self.push_child(second, reversed);
+                Some(&node.value)
+            }
         }
     }
 }
 
-impl<'a, V> IntoIterator for &'a ImplicitTreap<V> {
-    type IntoIter = IterRef<'a, V>;
+impl<'a, V, M: Monoid<V>> IntoIterator for &'a ImplicitTreap<V, M> {
+    type IntoIter = IterRef<'a, V, M>;
     type Item = &'a V;
     fn into_iter(self) -> Self::IntoIter {
         IterRef::new(self)
     }
 }
 
-impl<V> ImplicitTreap<V> {
-    pub fn iter(&self) -> IterRef<V> {
+impl<V, M: Monoid<V>> ImplicitTreap<V, M> {
+    pub fn iter(&self) -> IterRef<'_, V, M> {
         self.into_iter()
     }
 }
 
-impl<V: std::fmt::Display> std::ops::Index<usize> for ImplicitTreap<V> {
+impl<V, M: Monoid<V>> std::ops::Index<usize> for ImplicitTreap<V, M> {
     type Output = V;
 
     fn index(&self, index: usize) -> &Self::Output {
@@ -500,7 +649,8 @@ impl<V: std::fmt::Display> std::ops::Index<usize> for ImplicitTreap<V> {
     }
 }
 
-impl<V: std::fmt::Display> std::ops::IndexMut<usize> for ImplicitTreap<V> {
+/// Only without summaries, which would not be updated; see `update_at` otherwise
+impl<V> std::ops::IndexMut<usize> for ImplicitTreap<V> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
         let node_index = self.find(index);
         &mut self.nodes[node_index].value
@@ -511,24 +661,23 @@ impl<V: std::fmt::Display> std::ops::IndexMut<usize> for ImplicitTreap<V> {
 mod tests {
     use rand::{Rng, SeedableRng};
 
+    use super::*;
+
     #[test]
     fn test() {
-        let mut treap = super::ImplicitTreap::new();
-        treap.print_tree();
-        treap.print_vec();
+        let mut treap = ImplicitTreap::<i32>::new();
+        treap.check();
 
         for i in 1..10 {
-            println!("Inserting {i}");
             treap.push(i);
-            treap.print_tree();
-            treap.print_vec();
             treap.check();
         }
+        assert_eq!(format!("{treap:?}"), "[1, 2, 3, 4, 5, 6, 7, 8, 9]");
 
+        let mut expected = 9;
         while let Some(x) = treap.pop() {
-            println!("Removed {x:?}");
-            treap.print_tree();
-            treap.print_vec();
+            assert_eq!(x, expected);
+            expected -= 1;
             treap.check();
         }
     }
@@ -536,7 +685,7 @@ mod tests {
     #[test]
     fn big_test() {
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
-        let mut treap = super::ImplicitTreap::new();
+        let mut treap = super::ImplicitTreap::<u64>::new();
         let mut expected = Vec::new();
 
         // add some
@@ -570,4 +719,163 @@ mod tests {
         let actual: Vec<_> = treap.iter().copied().collect();
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_sequence_operations() {
+        let mut treap: ImplicitTreap<i64, Sum> = (0..10).collect();
+        let keys: Vec<NodeKey> = (0..10).map(|i| treap.find(i)).collect();
+
+        treap.reverse(2..7);
+        treap.check();
+        assert_eq!(format!("{treap:?}"), "[0, 1, 6, 5, 4, 3, 2, 7, 8, 9]");
+        assert_eq!(treap.node_index(keys[6]), 2);
+        assert_eq!(treap.find(3), keys[5]);
+        assert_eq!(treap.aggregate(1..4), 12);
+
+        treap.rotate_left(3);
+        treap.check();
+        assert_eq!(format!("{treap:?}"), "[5, 4, 3, 2, 7, 8, 9, 0, 1, 6]");
+        treap.rotate_right(2);
+        assert_eq!(format!("{treap:?}"), "[1, 6, 5, 4, 3, 2, 7, 8, 9, 0]");
+        assert_eq!(treap.node_index(keys[0]), 9);
+        assert_eq!(treap.aggregate(..), 45);
+
+        let mut copy = treap.clone();
+        let tail = copy.split_off(6);
+        copy.check();
+        tail.check();
+        assert_eq!(
+            format!("{copy:?} {tail:?}"),
+            "[1, 6, 5, 4, 3, 2] [7, 8, 9, 0]"
+        );
+        copy.extend([10, 11]);
+        copy.append(tail);
+        copy.check();
+        assert_eq!(
+            format!("{copy:?}"),
+            "[1, 6, 5, 4, 3, 2, 10, 11, 7, 8, 9, 0]"
+        );
+        // the original is unchanged
+        assert_eq!(treap.len(), 10);
+
+        treap.update_at(9, |value| *value = 100);
+        assert_eq!(treap.aggregate(7..), 117);
+
+        let mut treap: ImplicitTreap<i64, Min> = [5, 3, 8, 1, 9].into_iter().collect();
+        assert_eq!(treap.aggregate(..3), Some(3));
+        assert_eq!(treap.aggregate(2..2), None);
+        treap.reverse(..);
+        assert_eq!(treap.aggregate(..2), Some(1));
+        assert_eq!(treap.get(1), Some(&1));
+    }
+
+    /// The values as the digits of a number in base 100, which depends on their order
+    struct Digits;
+
+    impl Monoid<u32> for Digits {
+        // the number, and 100 to the power of the number of digits
+        type Summary = (u64, u64);
+        fn identity() -> (u64, u64) {
+            (0, 1)
+        }
+        fn summarize(value: &u32) -> (u64, u64) {
+            (*value as u64, 100)
+        }
+        fn combine(a: &(u64, u64), b: &(u64, u64)) -> (u64, u64) {
+            (
+                a.0.wrapping_mul(b.1).wrapping_add(b.0),
+                a.1.wrapping_mul(b.1),
+            )
+        }
+    }
+
+    fn digits(values: &[u32]) -> (u64, u64) {
+        values.iter().fold(Digits::identity(), |digits, value| {
+            Digits::combine(&digits, &Digits::summarize(value))
+        })
+    }
+
+    // apply random operations to a treap and a vector, and check that they stay the same
+    fn compare_with_vec(seed: u64, operations: usize) {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let treap_rng = rand::rngs::StdRng::seed_from_u64(rng.gen());
+        let mut treap = ImplicitTreap::<u32, Digits>::with_rng(treap_rng);
+        let mut expected: Vec<u32> = Vec::new();
+        for step in 0..operations {
+            let len = expected.len();
+            let context = format!("seed {seed}, step {step}");
+            match rng.gen_range(0..8) {
+                0 | 1 => {
+                    let (i, x) = (rng.gen_range(0..=len), rng.gen_range(0..100));
+                    treap.insert(i, x);
+                    expected.insert(i, x);
+                }
+                2 if len > 0 => {
+                    let i = rng.gen_range(0..len);
+                    assert_eq!(treap.remove_at(i), Some(expected.remove(i)), "{context}");
+                }
+                3 => {
+                    let (a, b) = (rng.gen_range(0..=len), rng.gen_range(0..=len));
+                    let (a, b) = (a.min(b), a.max(b));
+                    treap.reverse(a..b);
+                    expected[a..b].reverse();
+                }
+                4 => {
+                    let mid = rng.gen_range(0..=len);
+                    treap.rotate_left(mid);
+                    expected.rotate_left(mid);
+                }
+                5 => {
+                    let at = rng.gen_range(0..=len);
+                    let tail = treap.split_off(at);
+                    let mut expected_tail = expected.split_off(at);
+                    treap.append(tail);
+                    expected.append(&mut expected_tail);
+                }
+                6 if len > 0 => {
+                    let (i, x) = (rng.gen_range(0..len), rng.gen_range(0..100));
+                    treap.update_at(i, |value| *value = x);
+                    expected[i] = x;
+                }
+                _ => {
+                    let (a, b) = (rng.gen_range(0..=len), rng.gen_range(0..=len));
+                    let (a, b) = (a.min(b), a.max(b));
+                    let summary = digits(&expected[a..b]);
+                    assert_eq!(treap.aggregate(a..b), summary, "{context}");
+                }
+            }
+            if !expected.is_empty() {
+                let i = rng.gen_range(0..expected.len());
+                assert_eq!(treap.node_index(treap.find(i)), i, "{context}");
+                assert_eq!(treap[i], expected[i], "{context}");
+            }
+        }
+        treap.check();
+        let actual: Vec<_> = treap.iter().copied().collect();
+        assert_eq!(actual, expected, "seed {seed}");
+    }
+
+    #[test]
+    fn test_against_vec() {
+        // a failing seed can be replayed on its own
+        for seed in 0..200 {
+            compare_with_vec(seed, 300);
+        }
+    }
+
+    #[test]
+    fn test_seed() {
+        // same seed, same tree
+        let a: ImplicitTreap<u32> = (0..100).collect();
+        let b: ImplicitTreap<u32> = (0..100).collect();
+        let priorities = |treap: &ImplicitTreap<u32>| -> Vec<u64> {
+            (0..100)
+                .map(|i| treap.nodes[treap.find(i)].priority)
+                .collect()
+        };
+        assert_eq!(priorities(&a), priorities(&b));
+        let mut c = ImplicitTreap::with_rng(StdRng::seed_from_u64(7));
+        c.extend(0..100);
+        assert_ne!(priorities(&a), priorities(&c));
+    }
 }
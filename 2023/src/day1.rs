@@ -1,3 +1,8 @@
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 const DIGIT_VALUES: [(&str, u32); 20] = [
     ("0", 0),
     ("1", 1),
@@ -26,7 +31,7 @@ pub fn part1(input: &str) -> u32 {
     for line in input.lines() {
         let mut digits = line.chars().filter(|c| c.is_ascii_digit());
         let first = digits.next().unwrap();
-        let last = digits.last().unwrap_or(first);
+        let last = digits.next_back().unwrap_or(first);
         let value: u32 = format!("{first}{last}").parse().unwrap();
         total += value;
     }
@@ -56,6 +61,27 @@ pub fn part2(input: &str) -> u32 {
     total
 }
 
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 1;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,8 @@
 use std::collections::HashSet;
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 type Position = (usize, usize);
 
@@ -125,7 +129,7 @@ pub fn part1(input: &str) -> usize {
     let start = grid.find_start();
     let mut pos = start;
     let mut dir = grid.start_dir(pos);
-    let mut steps = 0;
+    let mut steps: usize = 0;
     loop {
         (pos, dir) = grid.follow_pipe(pos, dir);
         steps += 1;
@@ -133,7 +137,7 @@ pub fn part1(input: &str) -> usize {
             break;
         }
     }
-    (steps + 1) / 2
+    steps.div_ceil(2)
 }
 
 pub fn part2(input: &str) -> usize {
@@ -183,6 +187,27 @@ pub fn part2(input: &str) -> usize {
     count
 }
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 10;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
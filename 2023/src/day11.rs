@@ -1,4 +1,8 @@
 use std::collections::HashSet;
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 fn complement(set: HashSet<usize>) -> HashSet<usize> {
     let min = *set.iter().min().unwrap();
@@ -70,6 +74,27 @@ pub fn part2(input: &str) -> usize {
     solve(input, 1000000)
 }
 
+pub struct Day11;
+
+impl Solution for Day11 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 11;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
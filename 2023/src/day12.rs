@@ -1,3 +1,9 @@
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+use aoc_common::trace;
+
 fn arrangements(springs: &[u8], groups: &[i32]) -> usize {
     // c[i][j] is the number of arrangements when looking at the first i springs and j groups
     let mut c = Vec::new();
@@ -66,12 +72,33 @@ pub fn part2(input: &str) -> usize {
         let groups: Vec<i32> = groups.split(',').map(|g| g.parse().unwrap()).collect();
         let groups = groups.repeat(5);
         let c = arrangements(&springs, &groups);
-        dbg!(line, c);
+        trace!("{line}: {c}");
         sum += c;
     }
     sum
 }
 
+pub struct Day12;
+
+impl Solution for Day12 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 12;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
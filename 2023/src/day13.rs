@@ -1,10 +1,8 @@
-fn transpose(block: &[&[u8]]) -> Vec<Vec<u8>> {
-    let rows = block.len();
-    let cols = block[0].len();
-    (0..cols)
-        .map(|j| (0..rows).map(|i| block[i][j]).collect())
-        .collect()
-}
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::grid::Grid;
+use aoc_common::solution::{Answer, Parameter, Parameters, Solution};
 
 fn hamming(a: &[u8], b: &[u8]) -> usize {
     a.iter()
@@ -13,12 +11,12 @@ fn hamming(a: &[u8], b: &[u8]) -> usize {
         .sum()
 }
 
-fn middle_row<R: AsRef<[u8]>>(block: &[R], d: usize) -> Option<usize> {
-    let rows = block.len();
+fn middle_row(block: &Grid<u8>, d: usize) -> Option<usize> {
+    let rows = block.rows();
     for middle in 1..rows {
         let width = middle.min(rows - middle);
         let h: usize = (0..width)
-            .map(|i| hamming(block[middle - 1 - i].as_ref(), block[middle + i].as_ref()))
+            .map(|i| hamming(block.row(middle - 1 - i), block.row(middle + i)))
             .sum();
         if h == d {
             return Some(middle);
@@ -30,11 +28,11 @@ fn middle_row<R: AsRef<[u8]>>(block: &[R], d: usize) -> Option<usize> {
 pub fn part12(input: &str, d: usize) -> usize {
     let mut sum = 0;
     for block in input.trim().split("\n\n") {
-        let block: Vec<&[u8]> = block.as_bytes().split(|b| *b == b'\n').collect();
+        let block = Grid::parse(block).unwrap();
         if let Some(middle) = middle_row(&block, d) {
             sum += middle * 100;
         } else {
-            let block = transpose(&block);
+            let block = block.transpose();
             let middle = middle_row(&block, d).unwrap();
             sum += middle;
         }
@@ -42,6 +40,29 @@ pub fn part12(input: &str, d: usize) -> usize {
     sum
 }
 
+pub struct Day13;
+
+impl Solution for Day13 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 13;
+    const PART1_PARAMETERS: &'static [Parameter] = &[Parameter::new::<usize>("smudges", "0")];
+    const PART2_PARAMETERS: &'static [Parameter] = &[Parameter::new::<usize>("smudges", "1")];
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, params: &Parameters) -> Answer<impl Display> {
+        Ok(part12(input, params.get("smudges")))
+    }
+
+    fn part2(input: &Self::Input<'_>, params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part12(input, params.get("smudges"))))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,15 +1,19 @@
-use std::collections::HashMap;
+use std::fmt::Display;
+
+use aoc_common::cycle::find_cycle;
+use aoc_common::error::ParseError;
+use aoc_common::grid::Grid;
+use aoc_common::solution::{Answer, Parameters, Solution};
+use aoc_common::trace;
 
 pub fn part1(input: &str) -> usize {
-    let grid: Vec<&[u8]> = input.trim().as_bytes().split(|b| *b == b'\n').collect();
-    let rows = grid.len();
-    let cols = grid[0].len();
+    let grid = Grid::parse(input).unwrap();
+    let rows = grid.rows();
     let mut total_load = 0;
-    for j in 0..cols {
+    for j in 0..grid.cols() {
         let mut stack_start = 0;
         let mut stack_size = 0;
-        for (i, row) in grid.iter().enumerate() {
-            let c = row[j];
+        for (i, &c) in grid.column(j).enumerate() {
             if c == b'#' {
                 stack_start = i + 1;
                 stack_size = 0;
@@ -22,20 +26,18 @@ pub fn part1(input: &str) -> usize {
     total_load
 }
 
-fn move_north(mut grid: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
-    let rows = grid.len();
-    let cols = grid[0].len();
-    for j in 0..cols {
+fn move_north(mut grid: Grid<u8>) -> Grid<u8> {
+    for j in 0..grid.cols() {
         let mut stack_start = 0;
         let mut stack_size = 0;
-        for i in 0..rows {
-            let c = grid[i][j];
+        for i in 0..grid.rows() {
+            let c = grid[(i, j)];
             if c == b'#' {
                 stack_start = i + 1;
                 stack_size = 0;
             } else if c == b'O' {
-                grid[i][j] = b'.';
-                grid[stack_start + stack_size][j] = b'O';
+                grid[(i, j)] = b'.';
+                grid[(stack_start + stack_size, j)] = b'O';
                 stack_size += 1;
             }
         }
@@ -43,67 +45,45 @@ fn move_north(mut grid: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
     grid
 }
 
-fn rotate(grid: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
-    let rows = grid.len();
-    let cols = grid[0].len();
-    let mut ret = Vec::new();
-    for j in 0..cols {
-        let mut row = Vec::new();
-        for i in 0..rows {
-            row.push(grid[rows - 1 - i][j]);
-        }
-        ret.push(row);
-    }
-    ret
+fn total_load(grid: &Grid<u8>) -> usize {
+    let rows = grid.rows();
+    grid.find_all(&b'O').map(|(i, _)| rows - i).sum()
 }
 
-fn total_load(grid: &[Vec<u8>]) -> usize {
-    let rows = grid.len();
-    let mut total_load = 0;
-    for (i, row) in grid.iter().enumerate() {
-        for c in row {
-            if c == &b'O' {
-                total_load += rows - i;
-            }
-        }
+fn cycle(mut grid: Grid<u8>) -> Grid<u8> {
+    for _ in 0..4 {
+        grid = move_north(grid);
+        grid = grid.rotate_clockwise();
     }
-    total_load
-}
-
-fn cycle(mut grid: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
-    grid = move_north(grid);
-    grid = rotate(grid);
-    grid = move_north(grid);
-    grid = rotate(grid);
-    grid = move_north(grid);
-    grid = rotate(grid);
-    grid = move_north(grid);
-    grid = rotate(grid);
     grid
 }
 
 pub fn part2(input: &str) -> usize {
-    let mut grid: Vec<Vec<u8>> = input
-        .trim()
-        .as_bytes()
-        .split(|b| *b == b'\n')
-        .map(|row| row.to_vec())
-        .collect();
-    println!("{}", total_load(&grid));
-    let mut seen = HashMap::new();
-    let cycle_target = 1000000000;
-    for cycle_count in 0..cycle_target {
-        if let Some(last_seen) = seen.insert(grid.clone(), cycle_count) {
-            let loop_length = cycle_count - last_seen;
-            let remaining = (cycle_target - cycle_count) % loop_length;
-            for _ in 0..remaining {
-                grid = cycle(grid);
-            }
-            break;
-        }
-        grid = cycle(grid);
+    let grid = Grid::parse(input).unwrap();
+    trace!("load before spinning: {}", total_load(&grid));
+    let spins = find_cycle(grid, |grid| cycle(grid.clone()), Grid::clone);
+    total_load(spins.nth(1000000000))
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 14;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
     }
-    total_load(&grid)
 }
 
 #[cfg(test)]
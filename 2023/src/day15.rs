@@ -1,4 +1,8 @@
 use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 fn hash(s: &[u8]) -> u8 {
     let mut ret = 0u8;
@@ -53,6 +57,27 @@ pub fn part2(input: &str) -> u64 {
     total
 }
 
+pub struct Day15;
+
+impl Solution for Day15 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 15;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
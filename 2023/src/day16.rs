@@ -1,4 +1,8 @@
 use std::collections::HashSet;
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 struct Position {
@@ -172,6 +176,27 @@ pub fn part2(input: &str) -> usize {
     v.into_iter().max().unwrap()
 }
 
+pub struct Day16;
+
+impl Solution for Day16 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 16;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
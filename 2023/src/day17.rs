@@ -1,7 +1,10 @@
-use std::cmp::Ordering;
-use std::collections::hash_map::Entry;
-use std::collections::{BinaryHeap, HashMap};
-use std::fmt::{Debug, Error, Formatter};
+use std::fmt::{Debug, Display, Error, Formatter};
+
+use aoc_common::animation::Animation;
+use aoc_common::error::ParseError;
+use aoc_common::grid::Grid;
+use aoc_common::search::{dijkstra, Search};
+use aoc_common::solution::{Answer, Parameter, Parameters, Solution};
 
 #[derive(Clone, Copy, Eq, Hash, PartialEq)]
 enum Direction {
@@ -69,117 +72,85 @@ struct State {
     straight_steps: usize,
 }
 
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
-struct HeatLossAndState {
-    heat_loss: usize,
-    state: State,
-}
-
-impl HeatLossAndState {
-    fn key(&self) -> usize {
-        usize::MAX - self.heat_loss
-        // NOTE: this assumes that the grid is square
-        // NOTE: this assumes that the grid contains no zeroes
-        // usize::MAX / 2 - self.heat_loss + self.state.position.i + self.state.position.j
-    }
-}
-
-impl PartialOrd for HeatLossAndState {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for HeatLossAndState {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.key().cmp(&other.key())
+fn frame(grid: &[&[u8]], path: &[State]) -> Grid<u8> {
+    let mut frame = Grid::from_rows(grid.iter().map(|row| row.to_vec()).collect());
+    // the starting block is not entered from any direction
+    for state in path.iter().skip(1) {
+        let arrow = format!("{:?}", state.direction).as_bytes()[0];
+        frame[(state.position.i, state.position.j)] = arrow;
     }
+    frame
 }
 
-/*
-fn show_path(grid: &[&[u8]], previous: &HashMap<State, State>, state: &State) {
-    let mut visited = HashMap::new();
-    let mut state = state;
-    visited.insert(state.position.clone(), state.direction);
-    while let Some(prev) = previous.get(state) {
-        state = prev;
-        visited.insert(state.position.clone(), state.direction);
-    }
-    for (i, row) in grid.iter().enumerate() {
-        for (j, c) in row.iter().enumerate() {
-            if let Some(direction) = visited.get(&Position { i, j }) {
-                print!("{direction:?}");
-            } else {
-                print!("{}", *c as char);
-            }
-        }
-        println!();
-    }
-}
-*/
-
-pub fn part12(input: &str, min_steps: usize, max_steps: usize) -> usize {
-    let grid: Vec<&[u8]> = input.trim().as_bytes().split(|b| *b == b'\n').collect();
+fn best_paths(grid: &[&[u8]], min_steps: usize, max_steps: usize) -> Search<State, usize> {
     let rows = grid.len();
     let cols = grid[0].len();
-    let mut q = BinaryHeap::new();
-    for direction in [
+    let starts = [
         Direction::Left,
         Direction::Up,
         Direction::Right,
         Direction::Down,
-    ] {
-        q.push(HeatLossAndState {
-            heat_loss: 0,
-            state: State {
-                position: Position { i: 0, j: 0 },
-                direction,
-                straight_steps: 1,
-            },
-        });
-    }
+    ]
+    .map(|direction| State {
+        position: Position { i: 0, j: 0 },
+        direction,
+        straight_steps: 1,
+    });
     let end = Position {
         i: rows - 1,
         j: cols - 1,
     };
-    let mut previous: HashMap<State, State> = HashMap::new();
-    while let Some(HeatLossAndState { heat_loss, state }) = q.pop() {
-        let State {
-            position,
-            direction,
-            straight_steps,
-        } = state.clone();
-        if position == end {
-            // show_path(&grid, &previous, &state);
-            return heat_loss;
-        }
+    let successors = |state: &State| {
         let mut candidates = Vec::new();
-        if straight_steps < max_steps {
-            candidates.push((direction, straight_steps + 1));
+        if state.straight_steps < max_steps {
+            candidates.push((state.direction, state.straight_steps + 1));
         }
-        if straight_steps >= min_steps {
-            candidates.push((direction.turn_left(), 1));
-            candidates.push((direction.turn_right(), 1));
+        if state.straight_steps >= min_steps {
+            candidates.push((state.direction.turn_left(), 1));
+            candidates.push((state.direction.turn_right(), 1));
         }
-        for (direction, straight_steps) in candidates {
-            if let Some(position) = direction.next_position(rows, cols, &position) {
-                let heat_loss = heat_loss + (grid[position.i][position.j] - b'0') as usize;
+        candidates
+            .into_iter()
+            .filter_map(|(direction, straight_steps)| {
+                let position = direction.next_position(rows, cols, &state.position)?;
+                let heat_loss = (grid[position.i][position.j] - b'0') as usize;
                 let next_state = State {
                     position,
                     direction,
                     straight_steps,
                 };
-                if let Entry::Vacant(e) = previous.entry(next_state.clone()) {
-                    e.insert(state.clone());
-                    q.push(HeatLossAndState {
-                        heat_loss,
-                        state: next_state,
-                    });
-                }
-            }
-        }
+                Some((next_state, heat_loss))
+            })
+            .collect::<Vec<_>>()
+    };
+    dijkstra(starts, successors, |state| state.position == end)
+}
+
+pub fn part12(input: &str, min_steps: usize, max_steps: usize) -> usize {
+    let grid: Vec<&[u8]> = input.trim().as_bytes().split(|b| *b == b'\n').collect();
+    best_paths(&grid, min_steps, max_steps).goal_cost().unwrap()
+}
+
+/// The path with the least heat loss, drawn one block at a time
+pub fn animate(input: &str, min_steps: usize, max_steps: usize) -> Animation {
+    let grid: Vec<&[u8]> = input.trim().as_bytes().split(|b| *b == b'\n').collect();
+    let search = best_paths(&grid, min_steps, max_steps);
+    let path = search.path(search.goal().unwrap()).unwrap();
+    let mut animation = Animation::new();
+    // darker blocks lose more heat
+    for digit in 1..=9u8 {
+        let level = 255 - 25 * digit;
+        animation = animation.with_color((b'0' + digit) as char, [level, level, level]);
+    }
+    for arrow in ['<', '^', '>', 'v'] {
+        animation = animation.with_color(arrow, [255, 64, 64]);
+    }
+    for (step, state) in path.iter().enumerate() {
+        let heat_loss = search.costs[state];
+        let caption = format!("Step {step}, heat loss {heat_loss}");
+        animation.push(caption, &frame(&grid, &path[..=step]));
     }
-    unreachable!()
+    animation
 }
 
 pub fn part1(input: &str) -> usize {
@@ -190,6 +161,43 @@ pub fn part2(input: &str) -> usize {
     part12(input, 4, 10)
 }
 
+pub struct Day17;
+
+impl Solution for Day17 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 17;
+    const PART1_PARAMETERS: &'static [Parameter] = &[
+        Parameter::new::<usize>("min_steps", "1"),
+        Parameter::new::<usize>("max_steps", "3"),
+    ];
+    const PART2_PARAMETERS: &'static [Parameter] = &[
+        Parameter::new::<usize>("min_steps", "4"),
+        Parameter::new::<usize>("max_steps", "10"),
+    ];
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, params: &Parameters) -> Answer<impl Display> {
+        Ok(part12(
+            input,
+            params.get("min_steps"),
+            params.get("max_steps"),
+        ))
+    }
+
+    fn part2(input: &Self::Input<'_>, params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part12(
+            input,
+            params.get("min_steps"),
+            params.get("max_steps"),
+        )))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -211,4 +219,13 @@ mod tests {
         assert_eq!(part2(EXAMPLE2), 55);
         assert_eq!(part2(INPUT), 1268);
     }
+
+    #[test]
+    fn test_animate() {
+        let animation = animate(EXAMPLE1, 1, 3);
+        let last = animation.frame(animation.len() - 1);
+        assert!([b'>', b'v'].contains(&last[(12, 12)]));
+        assert_eq!(last[(0, 0)], b'2');
+        assert!(animation.text_log().contains("heat loss 102\n"));
+    }
 }
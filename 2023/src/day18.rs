@@ -1,3 +1,8 @@
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum Direction {
     Left,
@@ -102,6 +107,27 @@ pub fn part2(input: &str) -> u64 {
     solve(&parse_trench(input, parse_line2))
 }
 
+pub struct Day18;
+
+impl Solution for Day18 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 18;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,7 +1,11 @@
-use std::collections::HashMap;
-use std::fmt::{Debug, Error, Formatter};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Display, Error, Formatter};
 
-#[derive(Clone, Copy, Eq, PartialEq)]
+use aoc_common::cuboid;
+use aoc_common::error::{ParseError, SolveError};
+use aoc_common::solution::{Answer, Parameter, Parameters, Solution};
+
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
 enum Category {
     /// Extremely cool looking
     X = 0,
@@ -43,7 +47,7 @@ impl Debug for Category {
     }
 }
 
-#[derive(Clone, Copy, Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
 enum Comparison {
     LessThan,
     GreaterThan,
@@ -108,29 +112,6 @@ impl<'a> Rule<'a> {
         }
     }
 
-    fn apply(&self, part: &Part) -> Option<&'a str> {
-        match self {
-            Rule::Conditional(rule) => {
-                let ConditionalRule {
-                    category,
-                    comparison,
-                    threshold,
-                    destination,
-                } = rule;
-                let rating = part.ratings[*category as usize];
-                let pass = match comparison {
-                    Comparison::LessThan => rating < *threshold,
-                    Comparison::GreaterThan => rating > *threshold,
-                };
-                pass.then_some(destination)
-            }
-            Rule::Unconditional(rule) => {
-                let UnconditionalRule { destination } = rule;
-                Some(destination)
-            }
-        }
-    }
-
     fn apply_set(&self, set: PartSet) -> (Option<(&'a str, PartSet)>, Option<PartSet>) {
         match self {
             Rule::Conditional(rule) => {
@@ -140,44 +121,16 @@ impl<'a> Rule<'a> {
                     threshold,
                     destination,
                 } = rule;
-                let start_rating = set.start_ratings[*category as usize];
-                let end_rating = set.end_ratings[*category as usize];
-                match comparison {
-                    Comparison::LessThan => {
-                        if end_rating < *threshold {
-                            // whole range is below threshold, whole set passes the condition
-                            (Some((destination, set)), None)
-                        } else if start_rating < *threshold {
-                            // part of the range is below the threshold, split the set
-                            let mut below = set.clone();
-                            let mut above = set;
-                            below.end_ratings[*category as usize] = *threshold - 1;
-                            // NOTE: above does include the threshold
-                            above.start_ratings[*category as usize] = *threshold;
-                            (Some((destination, below)), Some(above))
-                        } else {
-                            // whole range is above threshold, nothing passes the condition
-                            (None, Some(set))
-                        }
-                    }
+                let axis = *category as usize;
+                let threshold = *threshold as i64;
+                let (pass, fail) = match comparison {
+                    Comparison::LessThan => set.split(axis, threshold),
                     Comparison::GreaterThan => {
-                        if start_rating > *threshold {
-                            // whole range is above threshold, whole set passes the condition
-                            (Some((destination, set)), None)
-                        } else if end_rating > *threshold {
-                            // part of the range is above the threshold, split the set
-                            let mut above = set.clone();
-                            let mut below = set;
-                            above.start_ratings[*category as usize] = *threshold + 1;
-                            // NOTE: below does include the threshold
-                            below.end_ratings[*category as usize] = *threshold;
-                            (Some((destination, above)), Some(below))
-                        } else {
-                            // whole range is above threshold, nothing passes the condition
-                            (None, Some(set))
-                        }
+                        let (below, above) = set.split(axis, threshold + 1);
+                        (above, below)
                     }
-                }
+                };
+                (pass.map(|set| (*destination, set)), fail)
             }
             Rule::Unconditional(rule) => {
                 let UnconditionalRule { destination } = rule;
@@ -222,28 +175,6 @@ impl<'a> Workflow<'a> {
         let rules = rules.split(',').map(Rule::from).collect();
         (name, Workflow { rules })
     }
-
-    fn apply(&self, part: &Part) -> &'a str {
-        for rule in self.rules.iter() {
-            if let Some(destination) = rule.apply(part) {
-                return destination;
-            }
-        }
-        panic!("no rule matched the part");
-    }
-
-    fn apply_set(&self, mut set: PartSet) -> Vec<(&'a str, PartSet)> {
-        let mut ret = Vec::new();
-        for rule in self.rules.iter() {
-            let (pass, fail) = rule.apply_set(set);
-            ret.extend(pass);
-            let Some(next) = fail else {
-                break;
-            };
-            set = next;
-        }
-        ret
-    }
 }
 
 impl Debug for Workflow<'_> {
@@ -264,11 +195,8 @@ struct Part {
     ratings: [u64; Category::Size as usize],
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
-struct PartSet {
-    start_ratings: [u64; Category::Size as usize],
-    end_ratings: [u64; Category::Size as usize],
-}
+/// Parts with ratings in ranges, for each category in the order x, m, a, s
+pub type PartSet = cuboid::Box<4>;
 
 impl Part {
     fn from(s: &str) -> Self {
@@ -288,6 +216,173 @@ impl Part {
     }
 }
 
+/// Node of the decision graph of a compiled system
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Node {
+    Accept,
+    Reject,
+    Test {
+        category: Category,
+        comparison: Comparison,
+        threshold: u64,
+        pass: usize,
+        fail: usize,
+    },
+}
+
+const ACCEPT: usize = 0;
+const REJECT: usize = 1;
+
+/// Workflows flattened into a single graph of tests, without cycles
+#[derive(Debug)]
+struct DecisionGraph {
+    nodes: Vec<Node>,
+    start: usize,
+}
+
+impl DecisionGraph {
+    fn is_part_accepted(&self, part: &Part) -> bool {
+        let mut index = self.start;
+        loop {
+            match self.nodes[index] {
+                Node::Accept => break true,
+                Node::Reject => break false,
+                Node::Test {
+                    category,
+                    comparison,
+                    threshold,
+                    pass,
+                    fail,
+                } => {
+                    let rating = part.ratings[category as usize];
+                    let passes = match comparison {
+                        Comparison::LessThan => rating < threshold,
+                        Comparison::GreaterThan => rating > threshold,
+                    };
+                    index = if passes { pass } else { fail };
+                }
+            }
+        }
+    }
+
+    /// Disjoint sets covering the accepted parts of `bounds`
+    fn accepted(&self, bounds: PartSet) -> Vec<PartSet> {
+        let mut accepted = Vec::new();
+        let mut q = vec![(self.start, bounds)];
+        while let Some((index, set)) = q.pop() {
+            if set.is_empty() {
+                continue;
+            }
+            match self.nodes[index] {
+                Node::Accept => accepted.push(set),
+                Node::Reject => {}
+                Node::Test {
+                    category,
+                    comparison,
+                    threshold,
+                    pass,
+                    fail,
+                } => {
+                    let axis = category as usize;
+                    let threshold = threshold as i64;
+                    let (pass_set, fail_set) = match comparison {
+                        Comparison::LessThan => set.split(axis, threshold),
+                        Comparison::GreaterThan => {
+                            let (below, above) = set.split(axis, threshold + 1);
+                            (above, below)
+                        }
+                    };
+                    q.extend(pass_set.map(|set| (pass, set)));
+                    q.extend(fail_set.map(|set| (fail, set)));
+                }
+            }
+        }
+        accepted
+    }
+}
+
+struct Compiler<'s, 'a> {
+    system: &'s System<'a>,
+    nodes: Vec<Node>,
+    /// Index of each node, to share identical ones
+    indices: HashMap<Node, usize>,
+    /// Start node of each workflow, `None` while it is being compiled
+    workflows: HashMap<&'a str, Option<usize>>,
+}
+
+impl<'a> Compiler<'_, 'a> {
+    fn node(&mut self, node: Node) -> usize {
+        match node {
+            // the test does not matter
+            Node::Test { pass, fail, .. } if pass == fail => pass,
+            _ => *self.indices.entry(node).or_insert_with(|| {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }),
+        }
+    }
+
+    fn destination(&mut self, name: &'a str) -> Result<usize, String> {
+        match name {
+            "A" => Ok(ACCEPT),
+            "R" => Ok(REJECT),
+            name => self.workflow(name),
+        }
+    }
+
+    fn workflow(&mut self, name: &'a str) -> Result<usize, String> {
+        match self.workflows.get(name) {
+            Some(Some(start)) => return Ok(*start),
+            Some(None) => return Err(format!("workflow {name} leads back to itself")),
+            None => {}
+        }
+        let system = self.system;
+        let workflow = system
+            .workflows
+            .get(name)
+            .ok_or_else(|| format!("unknown workflow {name}"))?;
+        self.workflows.insert(name, None);
+        // build the chain of tests from the end
+        let mut next = None;
+        for rule in workflow.rules.iter().rev() {
+            next = Some(match rule {
+                Rule::Unconditional(UnconditionalRule { destination }) => {
+                    self.destination(destination)?
+                }
+                Rule::Conditional(ConditionalRule {
+                    category,
+                    comparison,
+                    threshold,
+                    destination,
+                }) => {
+                    let fail =
+                        next.ok_or_else(|| format!("workflow {name} ends with a condition"))?;
+                    let pass = self.destination(destination)?;
+                    self.node(Node::Test {
+                        category: *category,
+                        comparison: *comparison,
+                        threshold: *threshold,
+                        pass,
+                        fail,
+                    })
+                }
+            });
+        }
+        let start = next.ok_or_else(|| format!("workflow {name} has no rules"))?;
+        self.workflows.insert(name, Some(start));
+        Ok(start)
+    }
+}
+
+/// Findings about the rules of a system which are never used
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Diagnostics<'a> {
+    /// Rules which never send any part, by workflow name and index of the rule
+    pub dead_rules: Vec<(&'a str, usize)>,
+    /// Workflows which no part ever enters
+    pub unreachable_workflows: Vec<&'a str>,
+}
+
 struct System<'a> {
     workflows: HashMap<&'a str, Workflow<'a>>,
 }
@@ -298,67 +393,136 @@ impl<'a> System<'a> {
         System { workflows }
     }
 
-    fn is_part_accepted(&self, part: &Part) -> bool {
-        let mut workflow = &self.workflows["in"];
-        loop {
-            match workflow.apply(part) {
-                "A" => break true,
-                "R" => break false,
-                name => workflow = &self.workflows[name],
-            }
-        }
+    /// Decision graph equivalent to the workflows, or an error if they can send a part around in
+    /// circles or to a workflow which does not exist
+    fn compile(&self) -> Result<DecisionGraph, String> {
+        let mut compiler = Compiler {
+            system: self,
+            nodes: vec![Node::Accept, Node::Reject],
+            indices: HashMap::new(),
+            workflows: HashMap::new(),
+        };
+        let start = compiler.workflow("in")?;
+        Ok(DecisionGraph {
+            nodes: compiler.nodes,
+            start,
+        })
     }
 
-    fn acceptable_ratings(&self) -> u64 {
-        let set = PartSet {
-            start_ratings: [1; 4],
-            end_ratings: [4000; 4],
-        };
-        let mut count = 0;
-        let mut q = vec![("in", set)];
-        while let Some((state, set)) = q.pop() {
-            let workflow = &self.workflows[state];
-            let res = workflow.apply_set(set);
-            for (state, set) in res {
-                if state == "R" {
-                    continue;
-                } else if state == "A" {
-                    let mut c = 1;
-                    for category in [Category::X, Category::M, Category::A, Category::S] {
-                        let start_rating = set.start_ratings[category as usize];
-                        let end_rating = set.end_ratings[category as usize];
-                        c *= end_rating - start_rating + 1;
+    fn diagnose(&self, bounds: PartSet) -> Result<Diagnostics<'a>, String> {
+        // following sets of parts through the workflows only ends without cycles
+        self.compile()?;
+        let mut used_rules = HashSet::new();
+        let mut reached = HashSet::new();
+        let mut q = Vec::new();
+        if !bounds.is_empty() {
+            q.push(("in", bounds));
+        }
+        while let Some((name, mut set)) = q.pop() {
+            reached.insert(name);
+            for (index, rule) in self.workflows[name].rules.iter().enumerate() {
+                let (pass, fail) = rule.apply_set(set);
+                if let Some((destination, set)) = pass {
+                    used_rules.insert((name, index));
+                    if !matches!(destination, "A" | "R") {
+                        q.push((destination, set));
                     }
-                    // NOTE: we split sets without duplicating parts, so all sets are disjoint
-                    count += c;
-                } else {
-                    q.push((state, set));
+                }
+                let Some(next) = fail else {
+                    break;
+                };
+                set = next;
+            }
+        }
+
+        let mut names: Vec<&'a str> = self.workflows.keys().copied().collect();
+        names.sort_unstable();
+        let mut diagnostics = Diagnostics::default();
+        for name in names {
+            if !reached.contains(name) {
+                diagnostics.unreachable_workflows.push(name);
+                continue;
+            }
+            for index in 0..self.workflows[name].rules.len() {
+                if !used_rules.contains(&(name, index)) {
+                    diagnostics.dead_rules.push((name, index));
                 }
             }
         }
-        count
+        Ok(diagnostics)
     }
 }
 
-pub fn part1(input: &str) -> u64 {
+/// Sets of parts with ratings between `min` and `max` included
+pub fn rating_bounds(min: i64, max: i64) -> PartSet {
+    PartSet::from_inclusive([min; 4], [max; 4])
+}
+
+/// Accepted parts of `bounds`, as disjoint sets
+pub fn accepted_region(input: &str, bounds: PartSet) -> Result<Vec<PartSet>, String> {
+    let (workflows, _) = input
+        .trim()
+        .split_once("\n\n")
+        .unwrap_or((input.trim(), ""));
+    let graph = System::from(workflows).compile()?;
+    Ok(graph.accepted(bounds))
+}
+
+/// Rules and workflows of the input which no part of `bounds` goes through
+pub fn diagnose(input: &str, bounds: PartSet) -> Result<Diagnostics<'_>, String> {
+    let (workflows, _) = input
+        .trim()
+        .split_once("\n\n")
+        .unwrap_or((input.trim(), ""));
+    System::from(workflows).diagnose(bounds)
+}
+
+pub fn part1(input: &str) -> Result<u64, String> {
     let (workflows, parts) = input.trim().split_once("\n\n").unwrap();
-    let system = System::from(workflows);
-    parts
+    let graph = System::from(workflows).compile()?;
+    Ok(parts
         .split('\n')
         .map(Part::from)
-        .filter(|part| system.is_part_accepted(part))
+        .filter(|part| graph.is_part_accepted(part))
         .map(|part| part.ratings.iter().sum::<u64>())
-        .sum()
+        .sum())
 }
 
-pub fn part2(input: &str) -> u64 {
-    let (workflows, _) = input.trim().split_once("\n\n").unwrap();
-    let system = System::from(workflows);
-    system.acceptable_ratings()
+pub fn part2(input: &str, min: i64, max: i64) -> Result<u64, String> {
+    let accepted = accepted_region(input, rating_bounds(min, max))?;
+    Ok(accepted.iter().map(PartSet::volume).sum::<i64>() as u64)
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 19;
+    const PART2_PARAMETERS: &'static [Parameter] = &[
+        Parameter::new::<i64>("min", "1"),
+        Parameter::new::<i64>("max", "4000"),
+    ];
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input)?)
+    }
+
+    fn part2(input: &Self::Input<'_>, params: &Parameters) -> Option<Answer<impl Display>> {
+        let accepted = part2(input, params.get("min"), params.get("max"));
+        Some(accepted.map_err(SolveError::from))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_common::solution::Entry;
+
     use super::*;
 
     const EXAMPLE: &str = include_str!("../examples/day19.txt");
@@ -366,13 +530,55 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(EXAMPLE), 19114);
-        assert_eq!(part1(INPUT), 382440);
+        assert_eq!(part1(EXAMPLE).unwrap(), 19114);
+        assert_eq!(part1(INPUT).unwrap(), 382440);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(EXAMPLE), 167409079868000);
-        assert_eq!(part2(INPUT), 136394217540123);
+        assert_eq!(part2(EXAMPLE, 1, 4000).unwrap(), 167409079868000);
+        assert_eq!(part2(INPUT, 1, 4000).unwrap(), 136394217540123);
+    }
+
+    #[test]
+    fn test_accepted_region() {
+        let workflows = "in{x<10:A,m>5:R,a}\na{s<3:A,R}";
+        let region = accepted_region(workflows, rating_bounds(1, 20)).unwrap();
+        let volume: i64 = region.iter().map(PartSet::volume).sum();
+        // x < 10, or x >= 10 with m <= 5 and s < 3
+        assert_eq!(volume, 9 * 20 * 20 * 20 + 11 * 5 * 20 * 2);
+        for (i, set) in region.iter().enumerate() {
+            assert!(region[i + 1..].iter().all(|other| !set.intersects(other)));
+        }
+        // narrower bounds
+        let region = accepted_region(workflows, rating_bounds(10, 20)).unwrap();
+        assert!(region.is_empty());
+    }
+
+    #[test]
+    fn test_errors() {
+        let cycle = "in{x<10:a,A}\na{m>5:in,R}";
+        assert!(accepted_region(cycle, rating_bounds(1, 4000)).is_err());
+        assert!(diagnose(cycle, rating_bounds(1, 4000)).is_err());
+        let unknown = "in{x<10:a,A}";
+        assert!(accepted_region(unknown, rating_bounds(1, 4000)).is_err());
+
+        // through the runner, the errors are reported instead of panicking
+        let entry = Entry::new::<Day19>();
+        let input = format!("{cycle}\n\n{{x=1,m=6,a=1,s=1}}");
+        assert!(entry.run(&input, 1, &[]).is_err());
+        assert!(entry.run(&input, 2, &[]).is_err());
+    }
+
+    #[test]
+    fn test_diagnose() {
+        // x<5 comes after x<10, b is only reached from it, and c from nowhere
+        let workflows = "in{x<10:A,x<5:b,a}\na{s>4000:R,A}\nb{A}\nc{R}";
+        let diagnostics = diagnose(workflows, rating_bounds(1, 4000)).unwrap();
+        assert_eq!(diagnostics.dead_rules, [("a", 0), ("in", 1)]);
+        assert_eq!(diagnostics.unreachable_workflows, ["b", "c"]);
+
+        let diagnostics = diagnose(EXAMPLE, rating_bounds(1, 4000)).unwrap();
+        assert_eq!(diagnostics, Diagnostics::default());
     }
 }
@@ -1,8 +1,12 @@
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 fn part12(input: &str) -> (u32, u32) {
     let mut sum = 0;
     let mut total_power = 0;
-    let mut game_id = 1;
-    for line in input.lines() {
+    for (game_id, line) in (1..).zip(input.lines()) {
         let (_, sets) = line.split_once(": ").unwrap();
         let mut max_red = 0;
         let mut max_green = 0;
@@ -27,7 +31,6 @@ fn part12(input: &str) -> (u32, u32) {
         }
         let power = max_red * max_green * max_blue;
         total_power += power;
-        game_id += 1;
     }
     (sum, total_power)
 }
@@ -40,6 +43,27 @@ pub fn part2(input: &str) -> u32 {
     part12(input).1
 }
 
+pub struct Day2;
+
+impl Solution for Day2 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 2;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
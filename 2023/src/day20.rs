@@ -1,5 +1,10 @@
 use std::collections::HashMap;
 use std::collections::VecDeque;
+use std::fmt::Display;
+
+use aoc_common::dot::Dot;
+use aoc_common::error::{ParseError, SolveError};
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 enum Pulse {
@@ -75,34 +80,38 @@ impl<'a> Module<'a> {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
-struct Modules<'a> {
+pub struct Modules<'a> {
     modules: HashMap<&'a str, Module<'a>>,
 }
 
 impl<'a> Modules<'a> {
-    fn from(s: &'a str) -> Self {
+    fn from(s: &'a str) -> Result<Self, ParseError> {
         // parse input
         let mut modules = HashMap::new();
         let mut last_pulses = Vec::new();
-        for line in s.lines() {
-            let (source, destinations) = line.split_once(" -> ").unwrap();
+        for (i, line) in s.lines().enumerate() {
+            let error = |expected| ParseError::new(i + 1, line, expected);
+            let (source, destinations) = line
+                .split_once(" -> ")
+                .ok_or_else(|| error("\"source -> destination, destination\""))?;
             let destinations: Vec<&str> = destinations.split(", ").collect();
-            let type_ = source.as_bytes()[0];
+            let type_ = source.as_bytes().first().copied();
 
-            let name = if type_ == b'%' || type_ == b'&' {
+            let name = if type_ == Some(b'%') || type_ == Some(b'&') {
                 &source[1..]
-            } else {
-                assert_eq!(source, "broadcaster");
+            } else if source == "broadcaster" {
                 source
+            } else {
+                return Err(error("a module prefixed with % or &, or the broadcaster"));
             };
 
             for destination in destinations.iter().copied() {
                 last_pulses.push((destination, name));
             }
 
-            let module = if type_ == b'%' {
+            let module = if type_ == Some(b'%') {
                 Module::new_flipflop(destinations)
-            } else if type_ == b'&' {
+            } else if type_ == Some(b'&') {
                 Module::new_conjunction(destinations)
             } else {
                 Module::new_broadcaster(destinations)
@@ -120,20 +129,15 @@ impl<'a> Modules<'a> {
             }
         }
 
-        Modules { modules }
+        Ok(Modules { modules })
     }
 
-    fn press_button(&mut self) -> (usize, usize) {
-        let mut low_pulses = 0;
-        let mut high_pulses = 0;
+    /// Call `on_pulse(from, to, pulse)` for each pulse sent
+    fn press_button(&mut self, mut on_pulse: impl FnMut(&'a str, &'a str, Pulse)) {
         let mut q = VecDeque::new();
         q.push_back(("broadcaster", "button", Pulse::Low));
         while let Some((name, from, pulse)) = q.pop_front() {
-            if pulse == Pulse::Low {
-                low_pulses += 1;
-            } else {
-                high_pulses += 1;
-            }
+            on_pulse(from, name, pulse);
             if let Some(module) = self.modules.get_mut(name) {
                 if let Some(pulse) = module.apply(from, pulse) {
                     for destination in &module.destinations {
@@ -142,23 +146,47 @@ impl<'a> Modules<'a> {
                 }
             }
         }
-        (low_pulses, high_pulses)
+    }
+
+    /// Flip-flops are drawn as boxes, and conjunctions as diamonds
+    pub fn to_dot(&self) -> String {
+        let mut names: Vec<&str> = self.modules.keys().copied().collect();
+        names.sort();
+        let mut dot = Dot::digraph();
+        for name in names {
+            let module = &self.modules[name];
+            let shape = match module.ext {
+                ModuleExt::Broadcaster => "doublecircle",
+                ModuleExt::FlipFlop(_) => "box",
+                ModuleExt::Conjunction(_) => "diamond",
+            };
+            dot.node(name, &[("shape", shape)]);
+            for destination in &module.destinations {
+                dot.edge(name, destination, &[]);
+            }
+        }
+        dot.to_string()
     }
 }
 
-pub fn part1(input: &str) -> usize {
-    let mut modules = Modules::from(input);
+fn pulse_product(modules: &Modules) -> usize {
+    let mut modules = modules.clone();
 
     let mut low_pulses = 0;
     let mut high_pulses = 0;
     for _ in 0..1000 {
-        let (low, high) = modules.press_button();
-        low_pulses += low;
-        high_pulses += high;
+        modules.press_button(|_, _, pulse| match pulse {
+            Pulse::Low => low_pulses += 1,
+            Pulse::High => high_pulses += 1,
+        });
     }
     low_pulses * high_pulses
 }
 
+pub fn part1(input: &str) -> usize {
+    pulse_product(&Modules::from(input).unwrap())
+}
+
 fn gcd(a: i64, b: i64) -> i64 {
     if b == 0 {
         a
@@ -195,8 +223,93 @@ fn test_lcm_many() {
     );
 }
 
-pub fn part2(_input: &str) -> i64 {
-    lcm_many([3889, 3943, 3761, 3821].into_iter())
+/// Give up if the counters have not all been seen to wrap around after this many presses
+const MAX_PRESSES: i64 = 1 << 16;
+
+/// `rx` is fed by a single conjunction, which sends a low pulse once all its inputs last sent a
+/// high pulse. Each of these inputs is driven by an independent chain of flip-flops that counts
+/// the button presses, and sends a high pulse each time it reaches a certain number. So `rx`
+/// first receives a low pulse when all the counters wrap around at the same press.
+fn presses_until_rx(modules: &Modules) -> Result<i64, String> {
+    let feeders: Vec<&str> = modules
+        .modules
+        .iter()
+        .filter(|(_, module)| module.destinations.contains(&"rx"))
+        .map(|(&name, _)| name)
+        .collect();
+    let &[feeder] = &feeders[..] else {
+        return Err(format!(
+            "expected a single module sending pulses to rx, found {}",
+            feeders.len()
+        ));
+    };
+    let ModuleExt::Conjunction(conjunction) = &modules.modules[feeder].ext else {
+        return Err(format!(
+            "{feeder}, which sends pulses to rx, is not a conjunction"
+        ));
+    };
+    let counters: Vec<&str> = conjunction.last_pulse.keys().copied().collect();
+    if counters.is_empty() {
+        return Err(format!("{feeder}, which sends pulses to rx, has no inputs"));
+    }
+
+    // for each counter, the presses at which it sent a high pulse to the feeder
+    let mut high_pulses: HashMap<&str, Vec<i64>> = HashMap::new();
+    let mut modules = modules.clone();
+    for presses in 1..=MAX_PRESSES {
+        modules.press_button(|from, to, pulse| {
+            if to == feeder && pulse == Pulse::High {
+                high_pulses.entry(from).or_default().push(presses);
+            }
+        });
+        let counted = |counter: &&str| high_pulses.get(counter).is_some_and(|p| p.len() >= 2);
+        if counters.iter().all(counted) {
+            break;
+        }
+    }
+
+    let mut periods = Vec::new();
+    for counter in counters {
+        match high_pulses.get(counter).map(Vec::as_slice) {
+            Some(&[first, second, ..]) if second == 2 * first => periods.push(first),
+            Some(&[first, second, ..]) => {
+                return Err(format!(
+                    "{counter} sends high pulses at presses {first} and {second}, not periodically"
+                ))
+            }
+            _ => {
+                return Err(format!(
+                    "{counter} does not send two high pulses within {MAX_PRESSES} presses"
+                ))
+            }
+        }
+    }
+    Ok(lcm_many(periods.into_iter()))
+}
+
+pub fn part2(input: &str) -> i64 {
+    presses_until_rx(&Modules::from(input).unwrap()).unwrap()
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 20;
+
+    type Input<'a> = Modules<'a>;
+
+    fn parse(input: &str) -> Result<Modules<'_>, ParseError> {
+        Modules::from(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(pulse_product(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(presses_until_rx(input).map_err(SolveError::from))
+    }
 }
 
 #[cfg(test)]
@@ -214,70 +327,36 @@ mod tests {
         assert_eq!(part1(INPUT), 747304011);
     }
 
+    #[test]
+    fn test_to_dot() {
+        let dot = Modules::from(EXAMPLE2).unwrap().to_dot();
+        assert!(dot.starts_with("digraph {\n    \"a\" [shape=\"box\"];\n"));
+        assert!(dot.contains("    \"broadcaster\" [shape=\"doublecircle\"];\n"));
+        assert!(dot.contains("    \"inv\" [shape=\"diamond\"];\n"));
+        assert!(dot.contains("    \"con\" -> \"output\";\n"));
+    }
+
     #[test]
     fn test_part2() {
-        // See circuit.dia, and circuit.svg
-        //
-        // To turn rx to high, vr needs to turn to high
-        // To turn vr to high, bm, cl, tn and vr need to turn to high
-        // To turn bm, cl, tn and vr to high, respectively ds, dt, bd and cs need to turn to high
-        // So, we need to find the first time when ds, dt, bd and cs are first set to high
-        // I do not know why they are not directly connected to rx
-        //
-        // The DAG of dependency for each of ds, dt, bd and cs is independent for the other, save for
-        // the broadcast node
-        // In each, there is a chain of 12 flip-flop modules connected in series.
-        // The broadcast node is connected to the first node in the series.
-        // The state of the nodes changes with each button press:
-        //
-        // state: 0000 0000 0000
-        //
-        // broadcast sends LOW pulse to flip-flop 1
-        // flip-flop 1 changes its state to ON and sends a HIGH pulse to flip-flop 2
-        // flip-flop 2 ignores the HIGH pulse
-        // state: 1000 0000 0000
-        //
-        // broadcast sends low pulse to flip-flop 1
-        // flip-flop 1 changes its state to OFF and sends a LOW pulse to flip-flop 2
-        // flip-flop 2 changes its state to ON, and sends a HIGH pulse to flip-flop 3
-        // flip-flop 3 ignores the HIGH pulse
-        // state: 0100 0000 0000
-        //
-        // broadcast sends low pulse to flip-flop 1
-        // flip-flop 1 changes its state to ON and sends a HIGH pulse to flip-flop 2
-        // flip-flop 2 ignores the HIGH pulse
-        // state: 1100 0000 0000
-        //
-        // broadcast sends low pulse to flip-flop 1
-        // flip-flop 1 changes its state to OFF and sends a LOW pulse to flip-flop 2
-        // flip-flop 2 changes its state to OFF, and sends a LOW pulse to flip-flop 3
-        // flip-flop 3 changes its state to ON, and sends a HIGH pulse to flip-flop 3
-        // flip-flop 4 ignores the HIGH pulse
-        // state: 0010 0000 0000
-        //
-        // In short, the chain counts the number of times the button was pressed.
-        //
-        // Certain flip-flop modules are connected to ds/dt/bd/cs.
-        // The first flip-flop is always part of these.
-        // The first time ds/dt/bd/cs turns to HIGH, these flip-flop modules are HIGH, and all the
-        // other are LOW.
-        // This happen after a number of button presses corresponding to the binary number represented
-        // by the HIGH flip-flops.
-        // When this happen, the ds/dt/bd/cs conjunction module sends a LOW pulse to:
-        // - rx (through two modules); which turns HIGH and all four of ds, dt, bd and cs do so
-        // - all of the LOW flip-flops, turning them HIGH as well, making the number 1111 1111 1111
-        // - the first flip-flop, incrementing that number, and thus resetting all 12 flip-flops
-        //
-        // In short, ds/dt/bd/cs turns HIGH when the number of button presses is a multiple of a
-        // certain number.
-        // This number is:
-        // ds: 3889
-        // dt: 3943
-        // bd: 3761
-        // cs: 3821
-        //
-        // The first time rx turns to HIGH corresponds to the first time all four of ds/dt/bd/cs turn to HHIGH.
-        // So it first turns HIGH when the number of button presses is the a smallest number which is a multiple of these four numbers.
         assert_eq!(part2(INPUT), 220366255099387);
+        let modules = Modules::from(EXAMPLE2).unwrap();
+        assert_eq!(
+            presses_until_rx(&modules).unwrap_err(),
+            "expected a single module sending pulses to rx, found 0"
+        );
+        let modules = Modules::from("broadcaster -> b\n%b -> c\n&ff -> rx\n").unwrap();
+        assert_eq!(
+            presses_until_rx(&modules).unwrap_err(),
+            "ff, which sends pulses to rx, has no inputs"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Modules::from("broadcaster -> a\n%a => b\n").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.text, "%a => b");
+        let error = Modules::from("broadcaster -> a\n%a -> b\nb -> a\n").unwrap_err();
+        assert_eq!(error.line, 3);
     }
 }
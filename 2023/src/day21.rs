@@ -1,4 +1,8 @@
 use std::collections::HashSet;
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameter, Parameters, Solution};
 
 fn reachable(grid: &[&[u8]], (si, sj): (usize, usize), steps: usize) -> usize {
     let rows = grid.len() as isize;
@@ -27,13 +31,13 @@ fn reachable(grid: &[&[u8]], (si, sj): (usize, usize), steps: usize) -> usize {
             }
         }
         (previous, current, next) = (current, next, previous);
-        if step % 2 == 0 {
+        if step.is_multiple_of(2) {
             evens += current.len();
         } else {
             odds += current.len();
         }
     }
-    if steps % 2 == 0 {
+    if steps.is_multiple_of(2) {
         evens
     } else {
         odds
@@ -124,6 +128,29 @@ pub fn part2(input: &str, steps: usize) -> usize {
     in_full_blocks + in_extremities + in_small_corners + in_big_corners
 }
 
+pub struct Day21;
+
+impl Solution for Day21 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 21;
+    const PART1_PARAMETERS: &'static [Parameter] = &[Parameter::new::<usize>("steps", "64")];
+    const PART2_PARAMETERS: &'static [Parameter] = &[Parameter::new::<usize>("steps", "26501365")];
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input, params.get("steps")))
+    }
+
+    fn part2(input: &Self::Input<'_>, params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input, params.get("steps"))))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
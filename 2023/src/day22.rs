@@ -1,163 +1,204 @@
-use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
-struct Coord {
-    x: u32,
-    y: u32,
-    z: u32,
-}
+use aoc_common::cuboid;
+use aoc_common::error::ParseError;
+use aoc_common::grid::Grid;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
-impl Coord {
-    fn from(s: &str) -> Coord {
-        let (x, yz) = s.split_once(',').unwrap();
-        let (y, z) = yz.split_once(',').unwrap();
-        let x = x.parse().unwrap();
-        let y = y.parse().unwrap();
-        let z = z.parse().unwrap();
-        assert!(x < 10);
-        assert!(y < 10);
-        Coord { x, y, z }
-    }
+/// Brick as a box of cubes, with x, y and z axes
+pub type Brick = cuboid::Box<3>;
+
+fn parse_brick(line: &str) -> Brick {
+    let parse = |s: &str| -> [i64; 3] {
+        let coords: Vec<i64> = s.split(',').map(|c| c.parse().unwrap()).collect();
+        coords.try_into().unwrap()
+    };
+    let (start, end) = line.split_once('~').unwrap();
+    let (start, end) = (parse(start), parse(end));
+    let min = std::array::from_fn(|axis| start[axis].min(end[axis]));
+    let max = std::array::from_fn(|axis| start[axis].max(end[axis]));
+    Brick::from_inclusive(min, max)
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
-struct Brick {
-    start: Coord,
-    end: Coord,
+/// Bricks once they have all fallen as low as they can
+#[derive(Clone, Debug)]
+pub struct Tower {
+    /// From bottom to top, so that bricks come after those they rest on
+    bricks: Vec<Brick>,
+    rests_on: Vec<Vec<usize>>,
+    holds_up: Vec<Vec<usize>>,
 }
 
-impl Brick {
-    fn from(line: &str) -> Self {
-        let (start, end) = line.split_once('~').unwrap();
-        let start = Coord::from(start);
-        let end = Coord::from(end);
-        assert!(start.z <= end.z);
-        Brick { start, end }
+impl Tower {
+    pub fn from(input: &str) -> Self {
+        Tower::settle(input.lines().map(parse_brick).collect())
     }
-}
 
-pub fn part1(input: &str) -> usize {
-    let mut bricks: Vec<_> = input.lines().map(Brick::from).collect();
-    bricks.sort_by_key(|brick| brick.start.z);
-
-    let mut is_unique_support = HashSet::new();
-    let mut summit = [[(0, None); 10]; 10];
-    let mut supports = HashSet::new();
-    for (i, brick) in bricks.iter().enumerate() {
-        let Brick { start, end } = brick;
-        // find where, and on which bricks this bricks will fall
-        supports.clear();
-        let mut max_height = 0;
-        for x in start.x..=end.x {
-            for y in start.y..=end.y {
-                let (height, support) = summit[x as usize][y as usize];
-                if height > max_height {
-                    supports.clear();
-                    max_height = height;
-                }
-                if height == max_height {
-                    if let Some(support) = support {
-                        supports.insert(support);
-                    }
-                }
+    fn settle(mut bricks: Vec<Brick>) -> Self {
+        bricks.sort_by_key(|brick| brick.start[2]);
+        Tower::stack(bricks)
+    }
+
+    /// Let the bricks fall one after the other, in the given order
+    fn stack(mut bricks: Vec<Brick>) -> Self {
+        // size the floor from the bricks
+        let rows = bricks.iter().map(|brick| brick.end[0]).max().unwrap_or(0);
+        let cols = bricks.iter().map(|brick| brick.end[1]).max().unwrap_or(0);
+        // lowest free level of each column, and the brick just below it
+        let mut summit = Grid::new(rows as usize, cols as usize, (1, None));
+        let mut rests_on = vec![Vec::new(); bricks.len()];
+        let mut holds_up = vec![Vec::new(); bricks.len()];
+        for (i, brick) in bricks.iter_mut().enumerate() {
+            let footprint: Vec<(usize, usize)> = (brick.start[0]..brick.end[0])
+                .flat_map(|x| (brick.start[1]..brick.end[1]).map(move |y| (x as usize, y as usize)))
+                .collect();
+            // find where, and on which bricks this brick will fall
+            let level = footprint.iter().map(|&pos| summit[pos].0).max().unwrap();
+            let mut supports: Vec<usize> = footprint
+                .iter()
+                .filter(|&&pos| summit[pos].0 == level)
+                .filter_map(|&pos| summit[pos].1)
+                .collect();
+            supports.sort_unstable();
+            supports.dedup();
+
+            *brick = brick.translate([0, 0, level - brick.start[2]]);
+            for &pos in &footprint {
+                summit[pos] = (brick.end[2], Some(i));
             }
-        }
-        // update the information about the highest bricks
-        let brick_h = brick.end.z - brick.start.z + 1;
-        for x in start.x..=end.x {
-            for y in start.y..=end.y {
-                summit[x as usize][y as usize] = (max_height + brick_h, Some(i));
+            for &support in &supports {
+                holds_up[support].push(i);
             }
+            rests_on[i] = supports;
         }
-        // update information about which bricks are unique supports
-        if supports.len() == 1 {
-            let support = supports.iter().copied().next().unwrap();
-            is_unique_support.insert(support);
+        Tower {
+            bricks,
+            rests_on,
+            holds_up,
         }
     }
-    bricks.len() - is_unique_support.len()
-}
 
-pub fn part2(input: &str) -> usize {
-    let mut bricks: Vec<_> = input.lines().map(Brick::from).collect();
-    bricks.sort_by_key(|brick| brick.start.z);
-
-    let mut is_supported_by = HashMap::new();
-    let mut is_support_for = HashMap::new();
-
-    let mut summit = [[(0, None); 10]; 10];
-    let mut supports = HashSet::new();
-    for (i, brick) in bricks.iter().enumerate() {
-        let Brick { start, end } = brick;
-        // find where, and on which bricks this bricks will fall
-        supports.clear();
-        let mut max_height = 0;
-        for x in start.x..=end.x {
-            for y in start.y..=end.y {
-                let (height, support) = summit[x as usize][y as usize];
-                if height > max_height {
-                    supports.clear();
-                    max_height = height;
-                }
-                if height == max_height {
-                    if let Some(support) = support {
-                        supports.insert(support);
-                    }
-                }
+    /// Settled bricks, from bottom to top
+    pub fn bricks(&self) -> &[Brick] {
+        &self.bricks
+    }
+
+    /// Bricks directly below brick `i`, which it rests on
+    pub fn rests_on(&self, i: usize) -> &[usize] {
+        &self.rests_on[i]
+    }
+
+    /// Bricks directly above brick `i`, which rest on it
+    pub fn holds_up(&self, i: usize) -> &[usize] {
+        &self.holds_up[i]
+    }
+
+    /// Tower left once the bricks of `removed` are gone and the others have fallen again; the
+    /// remaining bricks keep their order
+    pub fn without(&self, removed: &[usize]) -> Tower {
+        let bricks = self
+            .bricks
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !removed.contains(i))
+            .map(|(_, brick)| *brick)
+            .collect();
+        // bricks cannot pass through each other, so the order is still from bottom to top
+        Tower::stack(bricks)
+    }
+
+    /// For each brick, the number of other bricks which would fall if it were removed
+    pub fn falls_if_removed(&self) -> Vec<usize> {
+        // removing brick j makes brick i fall when all the paths from the ground to i go through
+        // j, i.e. when j dominates i; since bricks come after their supports, the immediate
+        // dominator of a brick is the lowest common ancestor of its supports in the dominator tree
+        let n = self.bricks.len();
+        let ground = n;
+        let levels = (usize::BITS - n.leading_zeros()) as usize + 1;
+        // `ancestors[k][i]` is the ancestor of `i` 2^k levels up
+        let mut ancestors = vec![vec![ground; n + 1]; levels];
+        let mut depth = vec![0; n + 1];
+        let mut dominator = vec![ground; n];
+        for i in 0..n {
+            let lca = |a, b| lowest_common_ancestor(&ancestors, &depth, a, b);
+            dominator[i] = self.rests_on[i]
+                .iter()
+                .copied()
+                .reduce(lca)
+                .unwrap_or(ground);
+            depth[i] = depth[dominator[i]] + 1;
+            ancestors[0][i] = dominator[i];
+            for k in 1..levels {
+                ancestors[k][i] = ancestors[k - 1][ancestors[k - 1][i]];
             }
         }
-        // update the information about the highest bricks
-        let brick_h = brick.end.z - brick.start.z + 1;
-        for x in start.x..=end.x {
-            for y in start.y..=end.y {
-                summit[x as usize][y as usize] = (max_height + brick_h, Some(i));
+
+        // a brick takes down its whole subtree
+        let mut sizes = vec![1; n];
+        for i in (0..n).rev() {
+            if dominator[i] != ground {
+                sizes[dominator[i]] += sizes[i];
             }
         }
-        // update information about which bricks support which
-        is_supported_by.insert(i, supports.iter().copied().collect::<Vec<_>>());
-        for support in supports.iter().copied() {
-            let supported = is_support_for.entry(support).or_insert_with(HashSet::new);
-            supported.insert(i);
-        }
+        sizes.into_iter().map(|size| size - 1).collect()
     }
+}
 
-    let mut total = 0;
-    let mut q = Vec::new();
-    let mut falls = HashSet::new();
-    for i in 0..bricks.len() {
-        let mut sub_total = 0;
-        falls.clear();
-        falls.insert(i);
-        q.clear();
-        let Some(supported) = is_support_for.get(&i) else {
-            continue;
-        };
-        for j in supported {
-            q.push(*j);
+fn lowest_common_ancestor(
+    ancestors: &[Vec<usize>],
+    depth: &[usize],
+    mut a: usize,
+    mut b: usize,
+) -> usize {
+    if depth[a] < depth[b] {
+        std::mem::swap(&mut a, &mut b);
+    }
+    for k in (0..ancestors.len()).rev() {
+        if depth[a] - depth[b] >= 1 << k {
+            a = ancestors[k][a];
         }
-        while let Some(i) = q.pop() {
-            // check whether there are remaining supports for i
-            let Some(supports) = is_supported_by.get(&i) else {
-                continue;
-            };
-            if !supports.iter().all(|support| falls.contains(support)) {
-                continue;
-            }
-            // i falls
-            falls.insert(i);
-            sub_total += 1;
-            // propagate the fall
-            let Some(supported) = is_support_for.get(&i) else {
-                continue;
-            };
-            for j in supported {
-                q.push(*j);
-            }
+    }
+    if a == b {
+        return a;
+    }
+    for k in (0..ancestors.len()).rev() {
+        if ancestors[k][a] != ancestors[k][b] {
+            a = ancestors[k][a];
+            b = ancestors[k][b];
         }
-        total += sub_total;
     }
+    ancestors[0][a]
+}
 
-    total
+pub fn part1(input: &str) -> usize {
+    let tower = Tower::from(input);
+    let falls = tower.falls_if_removed();
+    falls.into_iter().filter(|&count| count == 0).count()
+}
+
+pub fn part2(input: &str) -> usize {
+    Tower::from(input).falls_if_removed().into_iter().sum()
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 22;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
 }
 
 #[cfg(test)]
@@ -178,4 +219,36 @@ mod tests {
         assert_eq!(part2(EXAMPLE), 7);
         assert_eq!(part2(INPUT), 61555);
     }
+
+    #[test]
+    fn test_tower() {
+        let tower = Tower::from(EXAMPLE);
+        // A holds up B and C, which both hold up D and E
+        assert_eq!(tower.holds_up(0), [1, 2]);
+        assert_eq!(tower.rests_on(3), [1, 2]);
+        assert_eq!(tower.rests_on(0), []);
+        assert_eq!(tower.bricks()[6].start[2], 5);
+
+        // the fall counts match settling the tower again
+        for input in [EXAMPLE, INPUT] {
+            let tower = Tower::from(input);
+            let falls = tower.falls_if_removed();
+            for i in (0..tower.bricks().len()).step_by(37) {
+                let remaining = tower.bricks().iter().enumerate().filter(|&(j, _)| j != i);
+                let after = tower.without(&[i]);
+                let moved = remaining
+                    .zip(after.bricks())
+                    .filter(|((_, before), after)| before != after)
+                    .count();
+                assert_eq!(moved, falls[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_wide_floor() {
+        let tower = Tower::from("0,0,1~20,0,1\n15,0,3~15,15,3");
+        assert_eq!(tower.rests_on(1), [0]);
+        assert_eq!(tower.falls_if_removed(), [1, 0]);
+    }
 }
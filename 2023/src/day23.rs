@@ -1,4 +1,8 @@
 use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 type Coord = (usize, usize);
 
@@ -77,6 +81,7 @@ pub fn part2(input: &str) -> usize {
             }
         }
     }
+    #[allow(clippy::needless_range_loop)]
     for j in 0..cols {
         for i in 0..rows - 2 {
             if (grid[i][j], grid[i + 1][j], grid[i + 2][j]) == (b'v', b'.', b'v') {
@@ -160,6 +165,27 @@ pub fn part2(input: &str) -> usize {
     aux2(&graph, &mut visited, start, end).unwrap()
 }
 
+pub struct Day23;
+
+impl Solution for Day23 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 23;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,5 +1,9 @@
+use std::fmt::Display;
 use std::ops::{Add, Mul, Sub};
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameter, Parameters, Solution};
+
 #[derive(Clone, Debug, PartialEq)]
 struct Vec3 {
     x: f64,
@@ -185,6 +189,31 @@ pub fn part2(input: &str) -> f64 {
     }
 }
 
+pub struct Day24;
+
+impl Solution for Day24 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 24;
+    const PART1_PARAMETERS: &'static [Parameter] = &[
+        Parameter::new::<f64>("min", "200000000000000"),
+        Parameter::new::<f64>("max", "400000000000000"),
+    ];
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input, params.get("min"), params.get("max")))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
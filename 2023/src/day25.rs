@@ -1,114 +1,71 @@
-use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use aoc_common::cut::{min_cut, Cut};
+use aoc_common::dot::Dot;
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 type Edge<'a> = (&'a str, &'a str);
 
-fn insert_edge<'a>(edges: &mut HashMap<&'a str, Vec<&'a str>>, a: &'a str, b: &'a str) {
+fn parse_edges(input: &str) -> Vec<Edge<'_>> {
+    let mut edges = Vec::new();
+    for line in input.lines() {
+        let (node, neighbors) = line.split_once(": ").unwrap();
+        for neighbor in neighbors.split(' ') {
+            edges.push((node, neighbor));
+        }
+    }
     edges
-        .entry(a)
-        .and_modify(|e| e.push(b))
-        .or_insert_with(|| vec![b]);
 }
 
-fn randrange(state: &mut u64, max: u64) -> u64 {
-    // basic Linear Congruential Generator
-    *state = state.wrapping_mul(6364136223846793005) + 1;
-    *state % max
-}
-
-fn resolve_merged_node<'a>(merged_nodes: &HashMap<&'a str, &'a str>, mut node: &'a str) -> &'a str {
-    while let Some(p) = merged_nodes.get(&node) {
-        node = p;
+/// The 3 wires to disconnect to split the components in two groups
+pub fn three_wire_cut(input: &str) -> Result<Cut<&str>, String> {
+    let cut = min_cut(&parse_edges(input)).ok_or("there should be at least two components")?;
+    if cut.edges.len() != 3 {
+        return Err(format!(
+            "the smallest cut has {} wires instead of 3",
+            cut.edges.len()
+        ));
     }
-    node
+    Ok(cut)
 }
 
-fn min_cut<'a>(
-    graph: &'a HashMap<&'a str, Vec<&'a str>>,
-    mut edges: Vec<Edge<'a>>,
-    size: usize,
-) -> Vec<Edge<'a>> {
-    let mut state = 42;
-
-    // merged_nodes will serve as a union_find to quickly merge components
-    let mut merged_nodes: HashMap<&str, &str> = HashMap::new();
-    // keep track of removed edges to restore them after an attempt
-    let mut removed_edges = Vec::new();
-    loop {
-        // contract |V| - 2 pairs of nodes
-        for _ in 0..graph.len() - 2 {
-            loop {
-                let r = randrange(&mut state, edges.len() as u64);
-                let edge = edges.swap_remove(r as usize);
-                removed_edges.push(edge);
-                let (a, b) = edge;
-                let pa = resolve_merged_node(&merged_nodes, a);
-                let pb = resolve_merged_node(&merged_nodes, b);
-                if pa != pb {
-                    // merge a into b
-                    merged_nodes.insert(pa, pb);
-                    break;
-                }
-                // a and b have been merged together, find another edge
-            }
-        }
-        // check how many edges are remaining
-        let edge_count = edges
-            .iter()
-            .filter(|(a, b)| {
-                let pa = resolve_merged_node(&merged_nodes, a);
-                let pb = resolve_merged_node(&merged_nodes, b);
-                pa != pb
-            })
-            .count();
-        if edge_count == size {
-            // clean up the edges
-            edges.retain(|(a, b)| {
-                let pa = resolve_merged_node(&merged_nodes, a);
-                let pb = resolve_merged_node(&merged_nodes, b);
-                pa != pb
-            });
-            return edges;
+/// Render the components, with the 3 edges of the cut in red
+pub fn to_dot(input: &str) -> Result<String, String> {
+    let cut = three_wire_cut(input)?;
+    let mut dot = Dot::graph();
+    for (a, b) in parse_edges(input) {
+        if cut.edges.contains(&(a, b)) {
+            dot.edge(a, b, &[("color", "red")]);
+        } else {
+            dot.edge(a, b, &[]);
         }
-        // reset the graph
-        edges.append(&mut removed_edges);
-        merged_nodes.clear();
     }
+    Ok(dot.to_string())
 }
 
 pub fn part1(input: &str) -> usize {
-    let mut graph = HashMap::new();
-    let mut edges = Vec::new();
-    for line in input.lines() {
-        let (node, neighbors) = line.split_once(": ").unwrap();
-        for neighbor in neighbors.split(' ') {
-            edges.push((node, neighbor));
-            insert_edge(&mut graph, node, neighbor);
-            insert_edge(&mut graph, neighbor, node);
-        }
-    }
+    let [a, b] = three_wire_cut(input).unwrap().sizes();
+    a * b
+}
 
-    let edges = min_cut(&graph, edges, 3);
+pub struct Day25;
 
-    // find size of the components
-    let mut q = Vec::new();
-    q.push(graph.keys().next().unwrap());
-    let mut visited = HashSet::new();
-    while let Some(cur) = q.pop() {
-        if visited.contains(cur) {
-            continue;
-        }
-        visited.insert(cur);
-        for n in graph.get(cur).unwrap() {
-            if edges.contains(&(cur, n)) || edges.contains(&(n, cur)) {
-                continue;
-            }
-            q.push(n);
-        }
+impl Solution for Day25 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 25;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        let cut = three_wire_cut(input)?;
+        let [a, b] = cut.sizes();
+        Ok(a * b)
     }
-    assert_ne!(visited.len(), graph.len());
-    let a = visited.len();
-    let b = graph.len() - a;
-    a * b
 }
 
 #[cfg(test)]
@@ -123,4 +80,25 @@ mod tests {
         assert_eq!(part1(EXAMPLE), 54);
         assert_eq!(part1(INPUT), 555856);
     }
+
+    #[test]
+    fn test_three_wire_cut() {
+        let cut = three_wire_cut(EXAMPLE).unwrap();
+        let mut edges = cut.edges.clone();
+        edges.sort();
+        assert_eq!(edges, [("cmg", "bvb"), ("jqt", "nvd"), ("pzl", "hfx")]);
+        let mut sizes = cut.sizes();
+        sizes.sort();
+        assert_eq!(sizes, [6, 9]);
+        assert!(three_wire_cut("a: b c\nb: c\n").is_err());
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = to_dot(EXAMPLE).unwrap();
+        assert!(dot.starts_with("graph {\n    \"jqt\" -- \"rhn\";\n"));
+        assert!(dot.contains("    \"pzl\" -- \"hfx\" [color=\"red\"];\n"));
+        assert_eq!(dot.matches("red").count(), 3);
+        assert!(to_dot("a: b c\nb: c\n").is_err());
+    }
 }
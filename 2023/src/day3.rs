@@ -1,4 +1,8 @@
 use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 macro_rules! test_is_engine {
     ($b:ident, $lines:ident, $i:expr, $j:expr, $di:expr, $dj:expr) => {{
@@ -120,6 +124,27 @@ pub fn part2(input: &str) -> u32 {
         .sum()
 }
 
+pub struct Day3;
+
+impl Solution for Day3 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 3;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,8 @@
 use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 pub fn part1(input: &str) -> u32 {
     let mut score = 0;
@@ -24,8 +28,7 @@ pub fn part1(input: &str) -> u32 {
 pub fn part2(input: &str) -> u32 {
     let mut total_card_count = 0;
     let mut card_counts = HashMap::new();
-    let mut card = 1;
-    for line in input.lines() {
+    for (card, line) in (1..).zip(input.lines()) {
         let card_count = *card_counts.entry(card).and_modify(|e| *e += 1).or_insert(1);
         total_card_count += card_count;
         let (_, numbers) = line.split_once(": ").unwrap();
@@ -45,11 +48,31 @@ pub fn part2(input: &str) -> u32 {
                 .and_modify(|e| *e += card_count)
                 .or_insert(card_count);
         }
-        card += 1;
     }
     total_card_count
 }
 
+pub struct Day4;
+
+impl Solution for Day4 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 4;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
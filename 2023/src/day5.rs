@@ -1,181 +1,249 @@
-pub fn part1(input: &str) -> u64 {
-    let mut lines = input.lines();
+use std::fmt::Display;
+use std::ops::Range;
 
-    // read seeds
-    let seeds: Vec<u64> = {
-        let line = lines.next().unwrap();
-        let (_, seeds) = line.split_once(": ").unwrap();
-        seeds
-            .split_whitespace()
-            .map(|seed| seed.parse().unwrap())
-            .collect()
-    };
-    // empty line
-    lines.next().unwrap();
-
-    let mut maps = Vec::new();
-    'outer: loop {
-        // map name
-        let _ = lines.next().unwrap();
-
-        // rules
-        let mut map = Vec::new();
-        for line in lines.by_ref() {
-            if line.is_empty() {
-                // next map
-                maps.push(map);
-                continue 'outer;
-            }
-            let (starts, len) = line.rsplit_once(' ').unwrap();
-            let (dst_start, src_start) = starts.split_once(' ').unwrap();
+use aoc_common::error::{ParseError, SolveError};
+use aoc_common::interval::IntervalSet;
+use aoc_common::solution::{Answer, Parameters, Solution};
+use aoc_common::trace;
 
-            let dst_start: u64 = dst_start.parse().unwrap();
-            let src_start: u64 = src_start.parse().unwrap();
-            let len: u64 = len.parse().unwrap();
+/// Conversion from one category to another, as pieces `(source, destination)` which move the
+/// numbers of `source` so that it starts at `destination`; other numbers stay the same
+#[derive(Clone, Debug)]
+pub struct Map<'a> {
+    pub source: &'a str,
+    pub destination: &'a str,
+    /// Sorted by source, which do not overlap
+    pieces: Vec<(Range<u64>, u64)>,
+}
 
-            map.push((dst_start, src_start, len));
+impl<'a> Map<'a> {
+    fn from(s: &'a str) -> Self {
+        let mut lines = s.lines();
+        let name = lines.next().unwrap().strip_suffix(" map:").unwrap();
+        let (source, destination) = name.split_once("-to-").unwrap();
+        let mut pieces: Vec<(Range<u64>, u64)> = lines
+            .map(|line| {
+                let mut numbers = line.split_whitespace().map(|n| n.parse::<u64>().unwrap());
+                let dst_start = numbers.next().unwrap();
+                let src_start = numbers.next().unwrap();
+                let len = numbers.next().unwrap();
+                (src_start..src_start + len, dst_start)
+            })
+            .collect();
+        pieces.sort_unstable_by_key(|(source, _)| source.start);
+        // check that the mapping ranges do not overlap
+        for pair in pieces.windows(2) {
+            assert!(pair[0].0.end <= pair[1].0.start);
         }
-        // end of lines
-        assert!(lines.next().is_none());
-        maps.push(map);
-        break;
-    }
-    dbg!(&maps);
-
-    let mut numbers = Vec::new();
-    for mut number in seeds {
-        for map in maps.iter() {
-            let mut new_number = number;
-            for (dst_start, src_start, len) in map.iter() {
-                if (*src_start..=(*src_start + *len)).contains(&number) {
-                    new_number = dst_start + (number - src_start);
-                    break;
-                }
+        Map {
+            source,
+            destination,
+            pieces,
+        }
+    }
+
+    pub fn get(&self, number: u64) -> u64 {
+        let index = self
+            .pieces
+            .partition_point(|(source, _)| source.end <= number);
+        match self.pieces.get(index) {
+            Some((source, destination)) if source.start <= number => {
+                number - source.start + destination
             }
-            number = new_number;
+            _ => number,
         }
-        numbers.push(number);
     }
-    numbers.into_iter().min().unwrap()
-}
 
-pub fn part2(input: &str) -> u64 {
-    let mut lines = input.lines();
-
-    // read seeds
-    let mut ranges = Vec::new();
-    let line = lines.next().unwrap();
-    let (_, seeds) = line.split_once(": ").unwrap();
-    let mut numbers = seeds
-        .split_whitespace()
-        .map(|seed| seed.parse::<u64>().unwrap());
-    while let Some(start) = numbers.next() {
-        let len = numbers.next().unwrap();
-        ranges.push((start, len));
-    }
-    // empty line
-    lines.next().unwrap();
-
-    let mut maps = Vec::new();
-    'outer: loop {
-        // map name
-        let _ = lines.next().unwrap();
-
-        // rules
-        let mut map = Vec::new();
-        for line in lines.by_ref() {
-            if line.is_empty() {
-                // next map
-                map.sort_unstable_by_key(|(_dst_start, src_start, _len)| *src_start);
-
-                // check that the mapping ranges do not overlap
-                let mut max = 0;
-                for (_dst_start, src_start, len) in map.iter() {
-                    assert!(*src_start >= max);
-                    max = *src_start + *len;
+    /// Converted numbers, splitting the ranges on the boundaries of the pieces
+    pub fn get_ranges(&self, numbers: &IntervalSet<u64>) -> IntervalSet<u64> {
+        numbers.map_pieces(&self.pieces)
+    }
+
+    /// All the numbers, including those which stay the same, as sorted pieces
+    fn segments(&self) -> Vec<(Range<u64>, u64)> {
+        let mut segments = Vec::new();
+        let mut start = 0;
+        for (source, destination) in &self.pieces {
+            if start < source.start {
+                segments.push((start..source.start, start));
+            }
+            segments.push((source.clone(), *destination));
+            start = source.end;
+        }
+        if start < u64::MAX {
+            segments.push((start..u64::MAX, start));
+        }
+        segments
+    }
+
+    /// Conversion by `self`, then by `next`
+    pub fn then(&self, next: &Map<'a>) -> Map<'a> {
+        let next_segments = next.segments();
+        let mut pieces = Vec::new();
+        for (source, destination) in self.segments() {
+            // split the image of the segment on the segments of `next`
+            let image = destination..destination + (source.end - source.start);
+            for (next_source, next_destination) in &next_segments {
+                let start = image.start.max(next_source.start);
+                let end = image.end.min(next_source.end);
+                if start >= end {
+                    continue;
+                }
+                let piece_source =
+                    start - destination + source.start..end - destination + source.start;
+                let piece_destination = start - next_source.start + next_destination;
+                if piece_source.start != piece_destination {
+                    pieces.push((piece_source, piece_destination));
                 }
-                maps.push(map);
-                continue 'outer;
             }
+        }
+        Map {
+            source: self.source,
+            destination: next.destination,
+            pieces,
+        }
+    }
 
-            let (starts, len) = line.rsplit_once(' ').unwrap();
-            let (dst_start, src_start) = starts.split_once(' ').unwrap();
+    /// Smallest converted number of `numbers`, in time independent of its width
+    pub fn min_image(&self, numbers: Range<u64>) -> Option<u64> {
+        self.segments()
+            .into_iter()
+            .filter_map(|(source, destination)| {
+                let start = source.start.max(numbers.start);
+                let end = source.end.min(numbers.end);
+                (start < end).then(|| start - source.start + destination)
+            })
+            .min()
+    }
+}
 
-            let dst_start: u64 = dst_start.parse().unwrap();
-            let src_start: u64 = src_start.parse().unwrap();
-            let len: u64 = len.parse().unwrap();
+#[derive(Clone, Debug)]
+pub struct Almanac<'a> {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<Map<'a>>,
+}
 
-            map.push((dst_start, src_start, len));
+impl<'a> Almanac<'a> {
+    pub fn from(input: &'a str) -> Result<Self, ParseError> {
+        const EXPECTED: &str = "\"seeds: \" followed by pairs of a start and a length";
+        let mut sections = input.trim().split("\n\n");
+        let line = input.lines().next().unwrap_or_default();
+        let seeds = sections.next().unwrap_or_default();
+        let seeds = seeds
+            .strip_prefix("seeds: ")
+            .ok_or_else(|| ParseError::new(1, line, EXPECTED))?;
+        let seeds = seeds
+            .split_whitespace()
+            .map(|seed| aoc_common::error::parse(1, seed, "a number"))
+            .collect::<Result<Vec<u64>, _>>()?;
+        if seeds.len() % 2 != 0 {
+            return Err(ParseError::new(1, line, EXPECTED));
         }
-        map.sort_unstable_by_key(|(_dst_start, src_start, _len)| *src_start);
+        let maps = sections.map(Map::from).collect();
+        Ok(Almanac { seeds, maps })
+    }
 
-        // check that the mapping ranges do not overlap
-        let mut max = 0;
-        for (_dst_start, src_start, len) in map.iter() {
-            assert!(*src_start >= max);
-            max = *src_start + *len;
-        }
-        maps.push(map);
-        break;
-    }
-
-    for map in maps.iter() {
-        let mut new_ranges = Vec::new();
-        for (mut start, mut len) in ranges.iter() {
-            for (map_dst_start, map_src_start, map_len) in map {
-                if *map_src_start + *map_len <= start {
-                    // map is before range, do nothing
-                } else if *map_src_start <= start {
-                    // range starts in map range
-                    if start + len <= *map_src_start + *map_len {
-                        // range contained in map range, map the full range
-                        new_ranges.push((start - *map_src_start + *map_dst_start, len));
-                        // nothing more to map
-                        len = 0;
-                        break;
-                    } else {
-                        // range ends after map range, keep remaining range
-                        let mapped_range_len = *map_src_start + *map_len - start;
-                        new_ranges
-                            .push((start - *map_src_start + *map_dst_start, mapped_range_len));
-                        // remaining part
-                        start = *map_src_start + *map_len;
-                        len -= mapped_range_len;
-                    }
-                } else if *map_src_start <= start + len {
-                    // map range starts in range
-                    if *map_src_start + *map_len <= start + len {
-                        // map range ends in range
-                        // this part remains unmapped
-                        let unmapped_part_len = *map_src_start - start;
-                        new_ranges.push((start, unmapped_part_len));
-                        // mapped part
-                        new_ranges.push((*map_dst_start, *map_len));
-                        // remaining part
-                        start = start + unmapped_part_len + *map_len;
-                        len = len - unmapped_part_len - *map_len;
-                    } else {
-                        // map range ends after range
-                        // this part remains unmapped
-                        let unmapped_part_len = *map_src_start - start;
-                        new_ranges.push((start, unmapped_part_len));
-                        // mapped part
-                        new_ranges.push((*map_dst_start, len - unmapped_part_len));
-                        // nothing more to map
-                        len = 0;
-                        break;
-                    }
-                } else {
-                    // map is after range, do nothing
-                }
-            }
-            if len != 0 {
-                new_ranges.push((start, len));
+    /// Seeds read as pairs of a start and a length
+    pub fn seed_ranges(&self) -> Vec<Range<u64>> {
+        self.seeds
+            .chunks(2)
+            .map(|pair| pair[0]..pair[0] + pair[1])
+            .collect()
+    }
+
+    /// Maps to go from category `from` to category `to`
+    pub fn layers(&self, from: &str, to: &str) -> Result<Vec<&Map<'a>>, String> {
+        let mut layers = Vec::new();
+        let mut category = from;
+        while category != to {
+            let map = self
+                .maps
+                .iter()
+                .find(|map| map.source == category)
+                .ok_or_else(|| format!("no map from {category} towards {to}"))?;
+            if layers.len() == self.maps.len() {
+                return Err(format!("no way from {from} to {to}"));
             }
+            layers.push(map);
+            category = map.destination;
         }
-        ranges = new_ranges;
+        Ok(layers)
+    }
+
+    /// Single map from category `from` to category `to`
+    pub fn compose(&self, from: &'a str, to: &'a str) -> Result<Map<'a>, String> {
+        let identity = Map {
+            source: from,
+            destination: from,
+            pieces: Vec::new(),
+        };
+        let layers = self.layers(from, to)?;
+        Ok(layers
+            .into_iter()
+            .fold(identity, |map, next| map.then(next)))
+    }
+
+    /// Locations of the seeds in `seeds`, converting whole ranges at each layer
+    pub fn locations(&self, seeds: &IntervalSet<u64>) -> Result<IntervalSet<u64>, String> {
+        let layers = self.layers("seed", "location")?;
+        let mut numbers = seeds.clone();
+        for map in layers {
+            numbers = map.get_ranges(&numbers);
+            trace!("{}: {:?}", map.destination, numbers.ranges());
+        }
+        Ok(numbers)
+    }
+}
+
+/// Lowest location of the seeds, read one by one
+fn lowest_location(almanac: &Almanac) -> Result<u64, String> {
+    let seed_to_location = almanac.compose("seed", "location")?;
+    almanac
+        .seeds
+        .iter()
+        .map(|&seed| seed_to_location.get(seed))
+        .min()
+        .ok_or_else(|| "no seeds".to_string())
+}
+
+/// Lowest location of the seeds, read as ranges
+fn lowest_range_location(almanac: &Almanac) -> Result<u64, String> {
+    let seed_to_location = almanac.compose("seed", "location")?;
+    almanac
+        .seed_ranges()
+        .into_iter()
+        .filter_map(|seeds| seed_to_location.min_image(seeds))
+        .min()
+        .ok_or_else(|| "no seeds".to_string())
+}
+
+pub fn part1(input: &str) -> u64 {
+    lowest_location(&Almanac::from(input).unwrap()).unwrap()
+}
+
+pub fn part2(input: &str) -> u64 {
+    lowest_range_location(&Almanac::from(input).unwrap()).unwrap()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 5;
+
+    type Input<'a> = Almanac<'a>;
+
+    fn parse(input: &str) -> Result<Almanac<'_>, ParseError> {
+        Almanac::from(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(lowest_location(input)?)
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(lowest_range_location(input).map_err(SolveError::from))
     }
-    ranges.into_iter().min().unwrap().0
 }
 
 #[cfg(test)]
@@ -196,4 +264,40 @@ mod tests {
         assert_eq!(part2(EXAMPLE), 46);
         assert_eq!(part2(INPUT), 46294175);
     }
+
+    #[test]
+    fn test_almanac() {
+        let almanac = Almanac::from(EXAMPLE).unwrap();
+        let seed_to_location = almanac.compose("seed", "location").unwrap();
+        let locations: Vec<u64> = almanac
+            .seeds
+            .iter()
+            .map(|&seed| seed_to_location.get(seed))
+            .collect();
+        assert_eq!(locations, [82, 43, 86, 35]);
+        assert_eq!(seed_to_location.min_image(79..93), Some(46));
+
+        // converting ranges layer by layer gives the same locations as the composed map
+        for input in [EXAMPLE, INPUT] {
+            let almanac = Almanac::from(input).unwrap();
+            let seed_to_location = almanac.compose("seed", "location").unwrap();
+            for seeds in almanac.seed_ranges() {
+                let locations = almanac.locations(&IntervalSet::from(seeds.clone()));
+                let min = locations.unwrap().min();
+                assert_eq!(min, seed_to_location.min_image(seeds));
+            }
+        }
+
+        assert!(almanac.layers("location", "seed").is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let input = "seeds: 79 14 55\n\nseed-to-soil map:\n50 98 2\n";
+        let error = Almanac::from(input).err().unwrap();
+        assert_eq!(error.line, 1);
+        assert_eq!(error.text, "seeds: 79 14 55");
+        assert!(Almanac::from("seeds: 79 x\n").is_err());
+        assert!(Almanac::from("79 14\n").is_err());
+    }
 }
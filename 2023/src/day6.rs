@@ -1,3 +1,8 @@
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 fn parse_line1<'a, I: Iterator<Item = &'a str>>(lines: &mut I) -> Vec<u64> {
     lines
         .next()
@@ -25,7 +30,7 @@ pub fn part1(input: &str) -> u64 {
     let times = parse_line1(&mut lines);
     let distances = parse_line1(&mut lines);
     let mut ret = 1;
-    for (time, distance) in times.into_iter().zip(distances.into_iter()) {
+    for (time, distance) in times.into_iter().zip(distances) {
         ret *= race_winning_possibilities(time, distance);
     }
     ret
@@ -50,6 +55,27 @@ pub fn part2(input: &str) -> u64 {
     race_winning_possibilities(time, distance)
 }
 
+pub struct Day6;
+
+impl Solution for Day6 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 6;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,8 @@
 use std::collections::HashMap;
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 const STRENGTHS1: &[u8; 13] = b"AKQJT98765432";
 const STRENGTHS2: &[u8; 13] = b"AKQT98765432J";
@@ -153,6 +157,27 @@ pub fn part2(input: &str) -> usize {
         .sum()
 }
 
+pub struct Day7;
+
+impl Solution for Day7 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 7;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
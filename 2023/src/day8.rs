@@ -1,4 +1,9 @@
 use std::collections::HashMap;
+use std::fmt::Display;
+
+use aoc_common::cycle::find_cycle;
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 fn parse_input(data: &str) -> (&str, HashMap<&str, [&str; 2]>) {
     let mut lines = data.lines();
@@ -65,25 +70,25 @@ fn lcm_many<I: Iterator<Item = i64>>(it: I) -> i64 {
 
 fn detect_cycle(directions: &str, network: &HashMap<&str, [&str; 2]>, start: &str) -> i64 {
     assert!(start.ends_with('A'));
-    let mut node = start;
-    let mut visited = HashMap::new();
-    let mut ending_steps = Vec::new();
-    for (steps, (direction_step, direction)) in directions.chars().enumerate().cycle().enumerate() {
-        if let Some(last_seen) = visited.insert((direction_step, node), steps) {
-            let cycle_length = steps - last_seen;
-            // not guaranteed, but the input seem to verify it
-            assert_eq!(ending_steps.len(), 1);
-            let ending_offset = ending_steps[0];
-            // not guaranteed, but the input seem to verify it
-            assert_eq!(ending_offset, cycle_length);
-            return cycle_length as i64;
-        }
-        if node.ends_with('Z') {
-            ending_steps.push(steps);
-        }
-        node = network[node][if direction == 'L' { 0 } else { 1 }];
-    }
-    unreachable!();
+    let directions = directions.as_bytes();
+    let step = |&(direction_step, node): &(usize, &str)| {
+        let next = network[node][if directions[direction_step] == b'L' {
+            0
+        } else {
+            1
+        }];
+        ((direction_step + 1) % directions.len(), next)
+    };
+    let cycle = find_cycle((0, start), step, |&state| state);
+    let ending_steps: Vec<usize> = (0..cycle.start + cycle.period)
+        .filter(|&steps| cycle.nth(steps).1.ends_with('Z'))
+        .collect();
+    // not guaranteed, but the input seem to verify it
+    assert_eq!(ending_steps.len(), 1);
+    let ending_offset = ending_steps[0];
+    // not guaranteed, but the input seem to verify it
+    assert_eq!(ending_offset, cycle.period);
+    cycle.period as i64
 }
 
 pub fn part2(input: &str) -> i64 {
@@ -102,6 +107,27 @@ pub fn part2(input: &str) -> i64 {
     )
 }
 
+pub struct Day8;
+
+impl Solution for Day8 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 8;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,8 @@
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 fn next_of(numbers: &[i64]) -> i64 {
     if numbers.iter().all(|n| *n == 0) {
         0
@@ -44,6 +49,27 @@ pub fn part2(input: &str) -> i64 {
     sum
 }
 
+pub struct Day9;
+
+impl Solution for Day9 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 9;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
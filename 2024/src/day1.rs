@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 use std::fmt::Display;
 
+use aoc_common::error::{parse, ParseError};
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 fn analyze_digits(input: &str) -> Option<usize> {
     let line = input.lines().next()?;
     let line_length = line.len();
@@ -14,9 +17,13 @@ fn analyze_digits(input: &str) -> Option<usize> {
         return None;
     }
     let digits = left_digits;
+    if 2 * digits >= line_length {
+        // no room for spaces between the columns
+        return None;
+    }
     // check if input is multiple of line length
     // +1 for newline
-    if input.len() % (line_length + 1) != 0 {
+    if !input.len().is_multiple_of(line_length + 1) {
         // not a regular line length
         return None;
     }
@@ -49,36 +56,50 @@ fn analyze_digits(input: &str) -> Option<usize> {
     Some(digits)
 }
 
-fn parse_number(s: &[u8]) -> u64 {
-    s.iter().fold(0, |acc, c| acc * 10 + (*c - b'0') as u64)
+fn parse_number(s: &[u8]) -> Option<u64> {
+    s.iter().try_fold(0, |acc, c| {
+        c.is_ascii_digit().then(|| acc * 10 + (*c - b'0') as u64)
+    })
 }
 
-fn parse_slow(input: &str) -> (Vec<u64>, Vec<u64>) {
+type Lists = (Vec<u64>, Vec<u64>);
+
+fn parse_slow(input: &str) -> Result<Lists, ParseError> {
+    const EXPECTED: &str = "two numbers separated by spaces";
     let mut left = Vec::new();
     let mut right = Vec::new();
-    for line in input.lines() {
+    for (i, line) in input.lines().enumerate() {
         let mut parts = line.split_whitespace();
-        let l: u64 = parts.next().unwrap().parse().unwrap();
-        let r: u64 = parts.next().unwrap().parse().unwrap();
-        left.push(l);
-        right.push(r);
+        let (Some(l), Some(r), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(ParseError::new(i + 1, line, EXPECTED));
+        };
+        left.push(parse(i + 1, l, EXPECTED)?);
+        right.push(parse(i + 1, r, EXPECTED)?);
     }
-    (left, right)
+    Ok((left, right))
 }
 
+// the fast parsers only check the first lines of the input, so they fall back to the slow parser to
+// report any malformed line
 macro_rules! make_parse_digits {
     ($name:ident, $digits:expr) => {
-        fn $name(input: &str) -> (Vec<u64>, Vec<u64>) {
+        fn $name(input: &str) -> Result<Lists, ParseError> {
             let mut left = Vec::new();
             let mut right = Vec::new();
             for line in input.lines() {
                 let line = line.as_bytes();
-                let l: u64 = parse_number(&line[..$digits]);
-                let r: u64 = parse_number(&line[line.len() - $digits..]);
+                if line.len() < 2 * $digits {
+                    return parse_slow(input);
+                }
+                let l = parse_number(&line[..$digits]);
+                let r = parse_number(&line[line.len() - $digits..]);
+                let (Some(l), Some(r)) = (l, r) else {
+                    return parse_slow(input);
+                };
                 left.push(l);
                 right.push(r);
             }
-            (left, right)
+            Ok((left, right))
         }
     };
 }
@@ -105,7 +126,7 @@ make_parse_digits!(parse_18digits, 18);
 make_parse_digits!(parse_19digits, 19);
 make_parse_digits!(parse_20digits, 20);
 
-fn parse(input: &str) -> (Vec<u64>, Vec<u64>) {
+fn parse_lists(input: &str) -> Result<Lists, ParseError> {
     match analyze_digits(input) {
         Some(1) => parse_1digit(input),
         Some(2) => parse_2digits(input),
@@ -131,8 +152,7 @@ fn parse(input: &str) -> (Vec<u64>, Vec<u64>) {
     }
 }
 
-pub fn part1(input: &str) -> impl Display {
-    let (mut left, mut right) = parse(input);
+fn total_distance(mut left: Vec<u64>, mut right: Vec<u64>) -> u64 {
     left.sort();
     right.sort();
     left.into_iter()
@@ -141,6 +161,11 @@ pub fn part1(input: &str) -> impl Display {
         .sum::<u64>()
 }
 
+pub fn part1(input: &str) -> impl Display {
+    let (left, right) = parse_lists(input).unwrap();
+    total_distance(left, right)
+}
+
 fn part2_slow(left: Vec<u64>, right: Vec<u64>) -> u64 {
     let mut counts = HashMap::new();
     for v in right.into_iter() {
@@ -152,8 +177,11 @@ fn part2_slow(left: Vec<u64>, right: Vec<u64>) -> u64 {
 }
 
 fn part2_fast(left: Vec<u64>, right: Vec<u64>) -> u64 {
+    let Some(&max) = right.iter().max() else {
+        return 0;
+    };
     let mut counts = Vec::new();
-    let size = *right.iter().max().unwrap() as usize + 1;
+    let size = max as usize + 1;
     if counts.try_reserve(size).is_err() {
         return part2_slow(left, right);
     }
@@ -167,10 +195,34 @@ fn part2_fast(left: Vec<u64>, right: Vec<u64>) -> u64 {
 }
 
 pub fn part2(input: &str) -> impl Display {
-    let (left, right) = parse(input);
+    let (left, right) = parse_lists(input).unwrap();
     part2_fast(left, right)
 }
 
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 1;
+
+    type Input<'a> = Lists;
+
+    fn parse(input: &str) -> Result<Lists, ParseError> {
+        parse_lists(input)
+    }
+
+    fn part1((left, right): &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(total_distance(left.clone(), right.clone()))
+    }
+
+    fn part2(
+        (left, right): &Self::Input<'_>,
+        _params: &Parameters,
+    ) -> Option<Answer<impl Display>> {
+        Some(Ok(part2_fast(left.clone(), right.clone())))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -189,4 +241,19 @@ mod tests {
         assert_eq!(part2(EXAMPLE).to_string(), "31");
         assert_eq!(part2(INPUT).to_string(), "22776016");
     }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_lists("3   4\n4   3\n2   5\n1   x\n").unwrap_err();
+        assert_eq!(error.line, 4);
+        assert_eq!(error.text, "x");
+        let error = parse_lists("3   4\n4   3\n2   5\n1\n").unwrap_err();
+        assert_eq!(error.line, 4);
+        assert_eq!(error.text, "1");
+        let error = parse_lists("3 4 5\n").unwrap_err();
+        assert_eq!(error.line, 1);
+        let error = parse_lists("12345\n").unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (1, "12345"));
+        assert_eq!(part2("").to_string(), "0");
+    }
 }
@@ -1,6 +1,9 @@
 use std::collections::HashSet;
 use std::fmt::Display;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 pub fn part1(input: &str) -> impl Display {
     let grid: Vec<&[u8]> = input.trim().as_bytes().split(|&b| b == b'\n').collect();
     let rows = grid.len();
@@ -75,6 +78,27 @@ pub fn part2(input: &str) -> impl Display {
     score
 }
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 10;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
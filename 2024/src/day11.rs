@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 use std::fmt::Display;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 fn update_count(map: &mut HashMap<u64, u64>, key: u64, count: u64) {
     map.entry(key).and_modify(|c| *c += count).or_insert(count);
 }
@@ -40,6 +43,27 @@ pub fn part2(input: &str) -> impl Display {
     part12(input, 75)
 }
 
+pub struct Day11;
+
+impl Solution for Day11 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 11;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,9 @@
 use std::collections::HashSet;
 use std::fmt::Display;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 pub fn regions(input: &str) -> Vec<HashSet<(i64, i64)>> {
     let grid: Vec<&[u8]> = input.trim().as_bytes().split(|&b| b == b'\n').collect();
     let rows = grid.len() as i64;
@@ -105,6 +108,27 @@ pub fn part2(input: &str) -> impl Display {
     total
 }
 
+pub struct Day12;
+
+impl Solution for Day12 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 12;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,5 +1,8 @@
 use std::fmt::Display;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 fn parse_button(button: &str) -> (i128, i128) {
     let (_, xy) = button.split_once(": ").unwrap();
     let (x, y) = xy.split_once(", ").unwrap();
@@ -67,6 +70,27 @@ pub fn part2(input: &str) -> impl Display {
         .sum::<i128>()
 }
 
+pub struct Day13;
+
+impl Solution for Day13 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 13;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
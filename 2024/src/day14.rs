@@ -1,6 +1,10 @@
-use std::collections::HashSet;
 use std::fmt::Display;
 
+use aoc_common::animation::Animation;
+use aoc_common::error::{ParseError, SolveError};
+use aoc_common::grid::Grid;
+use aoc_common::solution::{Answer, Parameter, Parameters, Solution};
+
 pub fn parse(input: &str) -> Vec<(i64, i64, i64, i64)> {
     let mut posvel = Vec::new();
     for line in input.lines() {
@@ -60,60 +64,132 @@ pub fn quadrants_after_steps(input: &str, steps: usize, width: i64, height: i64)
     quadrants(&pos, width, height).into_iter().product()
 }
 
-fn has_bar(robots: &mut Vec<(i64, i64)>, threshold: usize) -> bool {
-    robots.sort();
-    let mut px = i64::MIN;
-    let mut py = i64::MIN;
-    let mut length = 0;
-    for &mut (x, y) in robots {
-        if (x, y) == (px, py + 1) {
-            length += 1;
-            if length >= threshold {
-                return true;
-            }
-        } else {
-            length = 0;
-        }
-        px = x;
-        py = y;
+/// Variance of the robot coordinates along one axis after `steps` seconds, times the square of
+/// the number of robots
+fn spread(positions: &[(i64, i64)], steps: i64, size: i64) -> i64 {
+    let n = positions.len() as i64;
+    let (sum, sum_squares) = positions
+        .iter()
+        .fold((0, 0), |(sum, sum_squares), &(p, v)| {
+            let x = (p + steps * v).rem_euclid(size);
+            (sum + x, sum_squares + x * x)
+        });
+    n * sum_squares - sum * sum
+}
+
+/// Number of seconds in `0..size` after which the robots are the most packed along one axis
+fn most_packed(positions: &[(i64, i64)], size: i64) -> i64 {
+    (0..size)
+        .min_by_key(|&steps| spread(positions, steps, size))
+        .unwrap()
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
     }
-    false
 }
 
-fn print(width: i64, height: i64, robots: &HashSet<(i64, i64)>) {
-    for y in 0..height {
-        for x in 0..width {
-            if robots.contains(&(x, y)) {
-                print!("#");
-            } else {
-                print!(".");
-            }
-        }
-        println!();
+fn check_room(width: i64, height: i64) -> Result<(), String> {
+    if width <= 0 || height <= 0 {
+        return Err(format!(
+            "the room should not be empty, got a width of {width} and a height of {height}"
+        ));
     }
+    Ok(())
 }
 
-pub fn part1_example(input: &str) -> impl Display {
-    quadrants_after_steps(input, 100, 11, 7)
+/// First time the robots arrange into a picture, with the picture
+///
+/// Positions along each axis repeat with the size of the room along that axis, and the robots
+/// forming the picture make their spread minimal on both at once; the Chinese remainder theorem
+/// then gives the step.
+pub fn find_tree(input: &str, width: i64, height: i64) -> Result<(usize, Grid<bool>), String> {
+    check_room(width, height)?;
+    if gcd(width, height) != 1 {
+        return Err(format!(
+            "the width {width} and height {height} of the room should be coprime"
+        ));
+    }
+    let posvel = parse(input);
+    let xs: Vec<(i64, i64)> = posvel.iter().map(|&(px, _, vx, _)| (px, vx)).collect();
+    let ys: Vec<(i64, i64)> = posvel.iter().map(|&(_, py, _, vy)| (py, vy)).collect();
+    let x_steps = most_packed(&xs, width);
+    let y_steps = most_packed(&ys, height);
+    let steps = (0..height)
+        .map(|k| x_steps + k * width)
+        .find(|steps| steps % height == y_steps)
+        .unwrap() as usize;
+    let robots = step(&posvel, steps, width, height);
+    Ok((steps, frame(width, height, &robots)))
 }
 
-pub fn part1(input: &str) -> impl Display {
-    quadrants_after_steps(input, 100, 101, 103)
+/// Tiles with at least one robot
+pub fn frame(width: i64, height: i64, robots: &[(i64, i64)]) -> Grid<bool> {
+    let mut frame = Grid::new(height as usize, width as usize, false);
+    for &(x, y) in robots {
+        frame[(y as usize, x as usize)] = true;
+    }
+    frame
 }
 
-pub fn part2(input: &str) -> impl Display {
+/// Robots after each of the given numbers of seconds
+pub fn animate(
+    input: &str,
+    steps: impl IntoIterator<Item = usize>,
+    width: i64,
+    height: i64,
+) -> Animation {
     let posvel = parse(input);
-    let width = 101;
-    let height = 103;
-    let mut steps = 0;
-    loop {
-        let mut pos = step(&posvel, steps, width, height);
-        if has_bar(&mut pos, 10) {
-            let robots: HashSet<_> = pos.into_iter().collect();
-            print(width, height, &robots);
-            return steps;
-        }
-        steps += 1;
+    let mut animation = Animation::new().with_color('#', [0, 192, 0]);
+    for steps in steps {
+        let robots = step(&posvel, steps, width, height);
+        animation.push(
+            format!("After {steps} seconds:"),
+            &frame(width, height, &robots),
+        );
+    }
+    animation
+}
+
+pub fn part1(input: &str, width: i64, height: i64) -> impl Display {
+    quadrants_after_steps(input, 100, width, height)
+}
+
+pub fn part2(input: &str, width: i64, height: i64) -> impl Display {
+    find_tree(input, width, height).unwrap().0
+}
+
+pub struct Day14;
+
+const ROOM: &[Parameter] = &[
+    Parameter::new::<i64>("width", "101"),
+    Parameter::new::<i64>("height", "103"),
+];
+
+impl Solution for Day14 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 14;
+    const PART1_PARAMETERS: &'static [Parameter] = ROOM;
+    const PART2_PARAMETERS: &'static [Parameter] = ROOM;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, params: &Parameters) -> Answer<impl Display> {
+        let (width, height) = (params.get("width"), params.get("height"));
+        check_room(width, height)?;
+        Ok(part1(input, width, height))
+    }
+
+    fn part2(input: &Self::Input<'_>, params: &Parameters) -> Option<Answer<impl Display>> {
+        let tree = find_tree(input, params.get("width"), params.get("height"));
+        Some(tree.map(|(steps, _)| steps).map_err(SolveError::from))
     }
 }
 
@@ -126,12 +202,29 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1_example(EXAMPLE).to_string(), "12");
-        assert_eq!(part1(INPUT).to_string(), "231782040");
+        assert_eq!(part1(EXAMPLE, 11, 7).to_string(), "12");
+        assert_eq!(part1(INPUT, 101, 103).to_string(), "231782040");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(INPUT).to_string(), "6475");
+        assert_eq!(part2(INPUT, 101, 103).to_string(), "6475");
+        let (steps, frame) = find_tree(INPUT, 101, 103).unwrap();
+        assert_eq!(steps, 6475);
+        // the frame around the tree
+        assert!(frame.to_string().contains(&"#".repeat(31)));
+        assert!(find_tree(INPUT, 100, 102).is_err());
+        assert!(find_tree(INPUT, 0, 1).is_err());
+        assert!(find_tree(INPUT, 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_animate() {
+        let animation = animate("p=2,4 v=2,-3\n", 0..=5, 11, 7);
+        assert_eq!(animation.len(), 6);
+        assert_eq!(animation.frame(5)[(3, 1)], b'#');
+        assert!(animation
+            .text_log()
+            .starts_with("After 0 seconds:\n...........\n"));
     }
 }
@@ -1,5 +1,10 @@
 use std::fmt::Display;
 
+use aoc_common::animation::Animation;
+use aoc_common::error::ParseError;
+use aoc_common::grid::Grid;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 fn find_start(map: &[&mut [u8]]) -> (i32, i32) {
     for (i, row) in map.iter().enumerate() {
         for (j, &cell) in row.iter().enumerate() {
@@ -21,13 +26,17 @@ fn dir_to_didj(dir: u8) -> (i32, i32) {
     }
 }
 
-pub fn print_map(map: &[&mut [u8]]) {
-    for row in map {
-        println!("{}", String::from_utf8_lossy(row));
+fn frame(map: &[&mut [u8]]) -> Grid<u8> {
+    Grid::from_rows(map.iter().map(|row| row.to_vec()).collect())
+}
+
+fn record(animation: &mut Option<&mut Animation>, map: &[&mut [u8]], move_: u8) {
+    if let Some(animation) = animation {
+        animation.push(format!("Move {}:", move_ as char), &frame(map));
     }
 }
 
-pub fn part1(input: &str) -> impl Display {
+fn move_boxes(input: &str, mut animation: Option<&mut Animation>) -> usize {
     let (map, moves) = input.split_once("\n\n").unwrap();
     let mut map = map.as_bytes().to_vec();
     let mut map: Vec<&mut [u8]> = map.split_mut(|&b| b == b'\n').collect();
@@ -38,9 +47,7 @@ pub fn part1(input: &str) -> impl Display {
         if move_ == b'\n' {
             continue;
         }
-        //print_map(&map);
-        //println!("Move: {}", move_ as char);
-        //println!();
+        record(&mut animation, &map, move_);
         let (di, dj) = dir_to_didj(move_);
         let mut n = 1;
         loop {
@@ -70,6 +77,9 @@ pub fn part1(input: &str) -> impl Display {
         map[i as usize][j as usize] = b'.';
         (i, j) = (ni, nj);
     }
+    if let Some(animation) = animation {
+        animation.push("End:", &frame(&map));
+    }
     // sum GPS coordinates
     let mut total = 0;
     for (i, row) in map.iter().enumerate() {
@@ -136,7 +146,7 @@ fn do_move_v(map: &mut [&mut [u8]], i: i32, j: i32, di: i32) {
     }
 }
 
-pub fn part2(input: &str) -> impl Display {
+fn move_wide_boxes(input: &str, mut animation: Option<&mut Animation>) -> usize {
     let (map, moves) = input.split_once("\n\n").unwrap();
     let map = map
         .replace("#", "##")
@@ -151,9 +161,7 @@ pub fn part2(input: &str) -> impl Display {
         if move_ == b'\n' {
             continue;
         }
-        //print_map(&map);
-        //println!("move: {}", move_ as char);
-        //println!();
+        record(&mut animation, &map, move_);
         let (di, dj) = dir_to_didj(move_);
         if di == 0 {
             // horizontal move
@@ -226,6 +234,9 @@ pub fn part2(input: &str) -> impl Display {
             }
         }
     }
+    if let Some(animation) = animation {
+        animation.push("End:", &frame(&map));
+    }
     // sum GPS coordinates
     let mut total = 0;
     for (i, row) in map.iter().enumerate() {
@@ -238,6 +249,50 @@ pub fn part2(input: &str) -> impl Display {
     total
 }
 
+pub fn part1(input: &str) -> impl Display {
+    move_boxes(input, None)
+}
+
+pub fn part2(input: &str) -> impl Display {
+    move_wide_boxes(input, None)
+}
+
+/// Warehouse before each move of the robot, and at the end
+pub fn animate(input: &str, wide: bool) -> Animation {
+    let mut animation = Animation::new()
+        .with_color('@', [255, 64, 64])
+        .with_color('O', [255, 192, 0])
+        .with_color('[', [255, 192, 0])
+        .with_color(']', [255, 192, 0]);
+    if wide {
+        move_wide_boxes(input, Some(&mut animation));
+    } else {
+        move_boxes(input, Some(&mut animation));
+    }
+    animation
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 15;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -258,4 +313,26 @@ mod tests {
         assert_eq!(part2(EXAMPLE2).to_string(), "9021");
         assert_eq!(part2(INPUT).to_string(), "1509724");
     }
+
+    #[test]
+    fn test_animate() {
+        let animation = animate(EXAMPLE1, false);
+        assert_eq!(animation.len(), 16);
+        assert_eq!(
+            animation.frame(15).to_string(),
+            "\
+            ########\n\
+            #....OO#\n\
+            ##.....#\n\
+            #.....O#\n\
+            #.#O@..#\n\
+            #...O..#\n\
+            #...O..#\n\
+            ########\n\
+            "
+        );
+        assert!(animate(EXAMPLE2, true)
+            .text_log()
+            .starts_with("Move <:\n####################\n"));
+    }
 }
@@ -1,165 +1,88 @@
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::HashSet;
 use std::fmt::Display;
 
-fn find_pos(grid: &[&[u8]], target: u8) -> (i32, i32) {
-    for (i, row) in grid.iter().enumerate() {
-        for (j, &cell) in row.iter().enumerate() {
-            if cell == target {
-                return (i as i32, j as i32);
-            }
-        }
-    }
-    panic!("Target not found");
-}
+use aoc_common::error::ParseError;
+use aoc_common::grid::Grid;
+use aoc_common::search::{dijkstra, Search};
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 struct State {
-    i: i32,
-    j: i32,
-    di: i32,
-    dj: i32,
+    pos: (usize, usize),
+    dir: (isize, isize),
 }
 
-impl State {
-    fn new(i: i32, j: i32) -> Self {
-        Self { i, j, di: 0, dj: 1 }
-    }
-
-    fn rev(&self) -> Self {
-        Self {
-            i: self.i,
-            j: self.j,
-            di: -self.di,
-            dj: -self.dj,
+fn best_paths(grid: &Grid<u8>) -> Search<State, i32> {
+    let start = State {
+        pos: grid.find(&b'S').expect("Target not found"),
+        dir: (0, 1),
+    };
+    let end = grid.find(&b'E').expect("Target not found");
+    let successors = |state: &State| {
+        let State { pos, dir: (di, dj) } = *state;
+        let mut next = vec![
+            (
+                State {
+                    pos,
+                    dir: (dj, -di),
+                },
+                1000,
+            ),
+            (
+                State {
+                    pos,
+                    dir: (-dj, di),
+                },
+                1000,
+            ),
+        ];
+        if let Some(pos) = grid.offset(pos, (di, dj)) {
+            if grid[pos] != b'#' {
+                next.push((State { pos, dir: (di, dj) }, 1));
+            }
         }
-    }
+        next
+    };
+    dijkstra([start], successors, |state| state.pos == end)
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
-struct StateWithScore {
-    score: i32,
-    state: State,
+fn parse_grid(input: &str) -> Grid<u8> {
+    Grid::parse(input.trim()).unwrap()
 }
 
-// NOTE: inverted ordering to make it a min-heap
-impl PartialOrd for StateWithScore {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(other.score.cmp(&self.score))
-    }
-}
-impl Ord for StateWithScore {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.score.cmp(&self.score)
-    }
+pub fn part1(input: &str) -> impl Display {
+    best_paths(&parse_grid(input)).goal_cost().unwrap()
 }
 
-fn dijkstra(grid: &[&[u8]], start: &[State]) -> HashMap<State, i32> {
-    let rows = grid.len() as i32;
-    let cols = grid[0].len() as i32;
-    let mut q = BinaryHeap::new();
-    for state in start {
-        q.push(StateWithScore {
-            score: 0,
-            state: state.clone(),
-        });
-    }
-    let mut visited = HashMap::new();
-    while let Some(StateWithScore { score, state }) = q.pop() {
-        let State { i, j, di, dj } = state;
-        if !(0..rows).contains(&i) || !(0..cols).contains(&j) {
-            continue;
-        }
-        let c = grid[i as usize][j as usize];
-        if c == b'#' {
-            continue;
-        }
-        if visited.contains_key(&state) {
-            continue;
-        }
-        visited.insert(state.clone(), score);
-        q.push(StateWithScore {
-            score: score + 1,
-            state: State {
-                i: i + di,
-                j: j + dj,
-                di,
-                dj,
-            },
-        });
-        q.push(StateWithScore {
-            score: score + 1000,
-            state: State {
-                i,
-                j,
-                di: dj,
-                dj: -di,
-            },
-        });
-        q.push(StateWithScore {
-            score: score + 1000,
-            state: State {
-                i,
-                j,
-                di: -dj,
-                dj: di,
-            },
-        });
-    }
-    visited
+pub fn part2(input: &str) -> impl Display {
+    let search = best_paths(&parse_grid(input));
+    let spots: HashSet<(usize, usize)> = search
+        .on_optimal_paths(&search.goals)
+        .into_iter()
+        .map(|state| state.pos)
+        .collect();
+    spots.len()
 }
 
-fn get_best_score(scores: &HashMap<State, i32>, end: (i32, i32)) -> i32 {
-    scores
-        .iter()
-        .filter_map(|(state, &score)| {
-            if (state.i, state.j) == end {
-                Some(score)
-            } else {
-                None
-            }
-        })
-        .min()
-        .unwrap()
-}
+pub struct Day16;
 
-pub fn part1(input: &str) -> impl Display {
-    let grid: Vec<&[u8]> = input.trim().as_bytes().split(|&b| b == b'\n').collect();
-    let start = find_pos(&grid, b'S');
-    let end = find_pos(&grid, b'E');
-    let start = State::new(start.0, start.1);
-    let scores = dijkstra(&grid, &[start]);
-    get_best_score(&scores, end)
-}
+impl Solution for Day16 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 16;
 
-pub fn part2(input: &str) -> impl Display {
-    let grid: Vec<&[u8]> = input.trim().as_bytes().split(|&b| b == b'\n').collect();
-    let orientations = [(0, 1), (0, -1), (1, 0), (-1, 0)];
-    // forward
-    let start = find_pos(&grid, b'S');
-    let start = State::new(start.0, start.1);
-    let forward = dijkstra(&grid, &[start]);
-    // backward
-    let end = find_pos(&grid, b'E');
-    let ends: Vec<_> = orientations
-        .iter()
-        .map(|&(di, dj)| State {
-            i: end.0,
-            j: end.1,
-            di,
-            dj,
-        })
-        .collect();
-    let backward = dijkstra(&grid, &ends);
-    // count best spots
-    let best_score = get_best_score(&forward, end);
-    let mut spots = HashSet::new();
-    for (state, f) in forward {
-        let b = backward[&state.rev()];
-        if f + b == best_score {
-            spots.insert((state.i, state.j));
-        }
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
     }
-    spots.len()
 }
 
 #[cfg(test)]
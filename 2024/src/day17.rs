@@ -1,7 +1,11 @@
+use std::collections::HashSet;
 use std::fmt::Display;
 
-#[derive(Debug)]
-struct State {
+use aoc_common::error::{parse, ParseError, SolveError};
+use aoc_common::solution::{Answer, Parameters, Solution};
+
+#[derive(Clone, Debug)]
+pub struct State {
     registers: Vec<u64>,
     program: Vec<u64>,
     ip: usize,
@@ -9,28 +13,43 @@ struct State {
 }
 
 impl State {
-    fn from_input(input: &str) -> Self {
-        let (registers, program) = input.split_once("\n\n").unwrap();
-        let registers: Vec<u64> = registers
-            .lines()
-            .map(|line| {
-                let (_name, value) = line.split_once(": ").unwrap();
-                value.parse().unwrap()
-            })
-            .collect();
-        let (_label, program) = program.split_once(": ").unwrap();
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input.lines().enumerate();
+        let mut registers = Vec::new();
+        for name in ["A", "B", "C"] {
+            let (i, line) = lines.next().unwrap_or((registers.len(), ""));
+            let expected = format!("\"Register {name}: N\"");
+            let value = line
+                .strip_prefix(&format!("Register {name}: "))
+                .ok_or_else(|| ParseError::new(i + 1, line, &expected))?;
+            registers.push(parse(i + 1, value, &expected)?);
+        }
+        let (i, line) = lines.next().unwrap_or((registers.len(), ""));
+        if !line.is_empty() {
+            return Err(ParseError::new(i + 1, line, "an empty line"));
+        }
+        let (i, line) = lines.next().unwrap_or((registers.len() + 1, ""));
+        let expected = "\"Program: \" followed by comma-separated 3-bit numbers";
+        let program = line
+            .strip_prefix("Program: ")
+            .ok_or_else(|| ParseError::new(i + 1, line, expected))?;
         let program: Vec<u64> = program
-            .trim()
-            .as_bytes()
-            .split(|&b| b == b',')
-            .map(|b| (b[0] - b'0') as u64)
-            .collect();
-        Self {
+            .split(',')
+            .map(|number| match parse(i + 1, number, expected)? {
+                number @ 0..=7 => Ok(number),
+                _ => Err(ParseError::new(i + 1, number, expected)),
+            })
+            .collect::<Result<_, _>>()?;
+        if !program.len().is_multiple_of(2) {
+            let expected = "an even number of 3-bit numbers, as (opcode, operand) pairs";
+            return Err(ParseError::new(i + 1, line, expected));
+        }
+        Ok(Self {
             registers,
             program,
             ip: 0,
             output: Vec::new(),
-        }
+        })
     }
 
     fn opcode(&mut self) -> u64 {
@@ -118,12 +137,194 @@ impl State {
         self.registers[2] = 0;
         self.ip = 0;
         self.output.clear();
-        while self.ip < self.program.len() {
+        // a jump to the last number halts too, since there is no operand
+        while self.ip + 1 < self.program.len() {
             self.step();
         }
     }
 }
 
+/// Whether the operand of the instruction is a combo operand, rather than a literal
+fn takes_combo_operand(opcode: u64) -> bool {
+    matches!(opcode, 0 | 2 | 5 | 6 | 7)
+}
+
+const MNEMONICS: [&str; 8] = ["adv", "bxl", "bst", "jnz", "bxc", "out", "bdv", "cdv"];
+
+fn combo_operand_name(operand: u64) -> String {
+    match operand {
+        0..=3 => operand.to_string(),
+        4 => "A".to_string(),
+        5 => "B".to_string(),
+        6 => "C".to_string(),
+        _ => "reserved".to_string(),
+    }
+}
+
+/// Render one instruction, e.g. `bst A` for `2,4`
+fn disassemble_instruction(opcode: u64, operand: u64) -> String {
+    let mnemonic = MNEMONICS[opcode as usize];
+    match opcode {
+        0 | 2 | 5 | 6 | 7 => format!("{mnemonic} {}", combo_operand_name(operand)),
+        1 | 3 => format!("{mnemonic} {operand}"),
+        _ => mnemonic.to_string(),
+    }
+}
+
+/// Render the program with one instruction per line, prefixed with its address
+pub fn disassemble(program: &[u64]) -> String {
+    program
+        .chunks(2)
+        .enumerate()
+        .map(|(i, instruction)| match instruction {
+            &[opcode, operand] => format!(
+                "{:2}: {}\n",
+                2 * i,
+                disassemble_instruction(opcode, operand)
+            ),
+            _ => format!("{:2}: ???\n", 2 * i),
+        })
+        .collect()
+}
+
+/// Why the debugger gave back control
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Stop {
+    Halted,
+    /// About to execute the instruction at this address
+    Breakpoint(usize),
+    CycleLimit,
+    /// Same address and registers as before, so the program will never halt
+    InfiniteLoop,
+    /// The instruction at this address uses the reserved combo operand 7
+    ReservedOperand(usize),
+}
+
+/// Step-by-step execution of a program, with breakpoints and an optional trace
+pub struct Debugger {
+    state: State,
+    breakpoints: HashSet<usize>,
+    max_cycles: Option<usize>,
+    cycles: usize,
+    /// Addresses that jumps lead to, the only places where a loop can be detected
+    jump_targets: HashSet<usize>,
+    /// State at a jump target, compared with the following ones to detect a loop (Brent's
+    /// algorithm), so that memory does not grow with the length of the run
+    snapshot: Option<(usize, Vec<u64>)>,
+    /// Visits to jump targets since the snapshot was taken, and before taking the next one
+    visits: usize,
+    snapshot_period: usize,
+    trace: Option<Vec<String>>,
+}
+
+impl Debugger {
+    pub fn new(state: &State) -> Self {
+        Debugger {
+            state: state.clone(),
+            breakpoints: HashSet::new(),
+            max_cycles: None,
+            cycles: 0,
+            jump_targets: state
+                .program
+                .chunks(2)
+                .filter_map(|instruction| match instruction {
+                    &[3, target] => Some(target as usize),
+                    _ => None,
+                })
+                .collect(),
+            snapshot: None,
+            visits: 0,
+            snapshot_period: 1,
+            trace: None,
+        }
+    }
+
+    pub fn add_breakpoint(&mut self, address: usize) {
+        self.breakpoints.insert(address);
+    }
+
+    pub fn remove_breakpoint(&mut self, address: usize) {
+        self.breakpoints.remove(&address);
+    }
+
+    pub fn set_cycle_limit(&mut self, max_cycles: usize) {
+        self.max_cycles = Some(max_cycles);
+    }
+
+    /// Log each executed instruction with the registers after it
+    pub fn enable_trace(&mut self) {
+        self.trace.get_or_insert_with(Vec::new);
+    }
+
+    pub fn trace(&self) -> &[String] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    pub fn output(&self) -> String {
+        format_output(&self.state.output)
+    }
+
+    /// Execute a single instruction, ignoring breakpoints
+    pub fn step(&mut self) -> Option<Stop> {
+        let ip = self.state.ip;
+        if ip + 1 >= self.state.program.len() {
+            return Some(Stop::Halted);
+        }
+        if self
+            .max_cycles
+            .is_some_and(|max_cycles| self.cycles >= max_cycles)
+        {
+            return Some(Stop::CycleLimit);
+        }
+        let (opcode, operand) = (self.state.program[ip], self.state.program[ip + 1]);
+        if takes_combo_operand(opcode) && operand == 7 {
+            return Some(Stop::ReservedOperand(ip));
+        }
+        if self.jump_targets.contains(&ip) {
+            let registers = &self.state.registers;
+            if self.snapshot == Some((ip, registers.clone())) {
+                return Some(Stop::InfiniteLoop);
+            }
+            self.visits += 1;
+            if self.visits == self.snapshot_period {
+                self.snapshot = Some((ip, registers.clone()));
+                self.visits = 0;
+                self.snapshot_period *= 2;
+            }
+        }
+        self.state.step();
+        self.cycles += 1;
+        if let Some(trace) = &mut self.trace {
+            let [a, b, c] = self.state.registers[..] else {
+                unreachable!()
+            };
+            let instruction = disassemble_instruction(opcode, operand);
+            trace.push(format!("{ip:2}: {instruction:<8} A={a} B={b} C={c}"));
+        }
+        None
+    }
+
+    /// Execute until the program halts or stops at a breakpoint; the breakpoint at the current
+    /// address, if any, is skipped, so that calling this again resumes the execution
+    pub fn run(&mut self) -> Stop {
+        if let Some(stop) = self.step() {
+            return stop;
+        }
+        loop {
+            if self.breakpoints.contains(&self.state.ip) {
+                return Stop::Breakpoint(self.state.ip);
+            }
+            if let Some(stop) = self.step() {
+                return stop;
+            }
+        }
+    }
+}
+
 fn format_output(output: &[u64]) -> String {
     output
         .iter()
@@ -132,91 +333,144 @@ fn format_output(output: &[u64]) -> String {
         .join(",")
 }
 
+fn run(state: &State) -> Result<String, String> {
+    let mut debugger = Debugger::new(state);
+    match debugger.run() {
+        Stop::Halted => Ok(debugger.output()),
+        Stop::InfiniteLoop => Err("the program never halts".to_string()),
+        Stop::ReservedOperand(address) => Err(format!(
+            "instruction {address} uses the reserved combo operand 7"
+        )),
+        stop => unreachable!("no breakpoint nor cycle limit, but stopped with {stop:?}"),
+    }
+}
+
 pub fn part1(input: &str) -> impl Display {
-    let mut state = State::from_input(input);
-    state.evaluate(state.registers[0]);
-    format_output(&state.output)
-}
-
-pub fn part2_example(_input: &str) -> impl Display {
-    let numbers = [0, 3, 5, 4, 3, 0];
-
-    // The example contains the following pairs of numbers, which correspond to the sequence of
-    // instructions below:
-    // 0,3  A = A >> 3
-    // 5,4  output A % 8
-    // 3,0  if A != 0 goto 0
-
-    // That means that each tribit of A directly maps to a number of the output. We can easily
-    // reconstruct A from the output:
-    let mut a = 0;
-    for number in numbers.into_iter().rev() {
-        a = a << 3 | number;
-    }
-    // the program _starts_ by shifting A, so we need to shift one more time here
-    a << 3
-}
-
-pub fn part2(_input: &str) -> impl Display {
-    let numbers = [2, 4, 1, 1, 7, 5, 1, 5, 4, 3, 0, 3, 5, 5, 3, 0];
-
-    // My input contains the following pairs of numbers, which correspond to the sequence of
-    // instructions below:
-    // 2,4  B = A % 8
-    // 1,1  B = B ^ 1
-    // 7,5  C = A >> B
-    // 1,5  B = B ^ 5
-    // 4,3  B = B ^ C
-    // 0,3  A = A >> 3
-    // 5,5  output B % 8
-    // 3,0  if A != 0 goto 0
-
-    // This correspond to this pseudo-code, with `round_output` being defined below.
-    // ```
-    // do {
-    //     output round_output(A);
-    //     A = A >> 3
-    // } while (A != 0);
-    // ```
-    fn round_output(a: u64) -> u64 {
-        let b = a % 8;
-        let b = b ^ 1;
-        let c = a >> b;
-        let b = b ^ 5;
-        let b = b ^ c;
-        b % 8
-    }
-
-    // From this, we can see that there is one tribit in A for each number in the output.
-    //
+    run(&State::from_input(input).unwrap()).unwrap()
+}
+
+/// Check that the program is a single loop that outputs one number and shifts A by 3 each round,
+/// with B and C only depending on A
+fn check_loop_shape(program: &[u64]) -> Result<(), String> {
+    if !program.len().is_multiple_of(2) {
+        return Err("the program should be made of (opcode, operand) pairs".to_string());
+    }
+    let Some((body, [3, 0])) = program.split_last_chunk::<2>() else {
+        return Err("the program should end with a jump to the start (3,0)".to_string());
+    };
+    let mut shifts = 0;
+    let mut outputs = 0;
+    let mut b_set = false;
+    let mut c_set = false;
+    for (i, instruction) in body.chunks(2).enumerate() {
+        // same address as in `disassemble`
+        let address = 2 * i;
+        let (opcode, operand) = (instruction[0], instruction[1]);
+        let is_combo = takes_combo_operand(opcode);
+        let reads_b = (is_combo && operand == 5) || opcode == 1 || opcode == 4;
+        let reads_c = (is_combo && operand == 6) || opcode == 4;
+        if (reads_b && !b_set) || (reads_c && !c_set) {
+            return Err(format!(
+                "instruction {address} reads a register from the previous round"
+            ));
+        }
+        if is_combo && operand == 7 {
+            return Err(format!(
+                "instruction {address} uses the reserved combo operand 7"
+            ));
+        }
+        match opcode {
+            0 if operand == 3 => shifts += 1,
+            0 => return Err(format!("instruction {address} should shift A by 3")),
+            3 => {
+                return Err(format!(
+                    "instruction {address} jumps before the end of the program"
+                ))
+            }
+            5 => outputs += 1,
+            1 | 2 | 4 | 6 => b_set = true,
+            7 => c_set = true,
+            _ => (),
+        }
+    }
+    if shifts != 1 {
+        return Err(format!("the loop shifts A {shifts} times instead of once"));
+    }
+    if outputs != 1 {
+        return Err(format!("the loop outputs {outputs} numbers instead of one"));
+    }
+    Ok(())
+}
+
+fn smallest_quine(state: &State) -> Result<u64, String> {
+    check_loop_shape(&state.program)?;
+
+    // Each round of the loop outputs a number that depends only on A, and then shifts A by 3. So
+    // there is one tribit in A for each number in the output.
+    let mut round = state.clone();
+    round.program.truncate(state.program.len() - 2);
+
     // To reconstruct A, we start from the most significant tribit, which corresponds to the last
-    // number of the program. Only certain values of that tribit will output the required number (0
-    // here). We keep all possible values, and move to the previous number of the program.
+    // number of the program. Only certain values of that tribit will output the required number.
+    // We keep all possible values, and move to the previous number of the program.
     //
     // We then iterate for each number in reverse order, trying all possible values of the tribit
     // for each of the candidates of the previous round. We need to keep all the candidates, since
     // some of the candidates might not output a certain number for any tribit we add to it.
     let mut candidates = vec![0];
     let mut new_candidates = Vec::new();
-    for number in numbers.into_iter().rev() {
+    for (i, &number) in state.program.iter().enumerate().rev() {
         new_candidates.clear();
         for candidate in candidates.iter().copied() {
             for tribit in 0..8 {
                 let new_candidate = candidate << 3 | tribit;
-                if round_output(new_candidate) == number {
+                round.evaluate(new_candidate);
+                if round.output == [number] && round.registers[0] == candidate {
                     new_candidates.push(new_candidate);
                 }
             }
         }
+        // the loop stops as soon as A becomes zero
+        if i > 0 {
+            new_candidates.retain(|&candidate| candidate != 0);
+        }
         (candidates, new_candidates) = (new_candidates, candidates);
     }
-    // candidates are kept in sorted order, so the smallest possible solution is the first
-    // remaining candidate
-    candidates[0]
+    candidates
+        .into_iter()
+        .min()
+        .ok_or_else(|| "no value of A makes the program output itself".to_string())
+}
+
+pub fn part2(input: &str) -> impl Display {
+    smallest_quine(&State::from_input(input).unwrap()).unwrap()
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 17;
+
+    type Input<'a> = State;
+
+    fn parse(input: &str) -> Result<State, ParseError> {
+        State::from_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        run(input).map_err(SolveError::from)
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(smallest_quine(input).map_err(SolveError::from))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_common::solution::Entry;
+
     use super::*;
 
     const EXAMPLE1: &str = include_str!("../examples/day17-1.txt");
@@ -231,7 +485,111 @@ mod tests {
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2_example(EXAMPLE2).to_string(), "117440");
+        assert_eq!(part2(EXAMPLE2).to_string(), "117440");
         assert_eq!(part2(INPUT).to_string(), "164541017976509");
     }
+
+    #[test]
+    fn test_loop_shape() {
+        let state = State::from_input(EXAMPLE1).unwrap();
+        assert_eq!(
+            smallest_quine(&state).unwrap_err(),
+            "instruction 0 should shift A by 3"
+        );
+        let program = [2, 4, 1, 1, 7, 5, 5, 5, 3, 0];
+        assert_eq!(
+            check_loop_shape(&program).unwrap_err(),
+            "the loop shifts A 0 times instead of once"
+        );
+        let program = [0, 3, 5, 5, 3, 0];
+        assert_eq!(
+            check_loop_shape(&program).unwrap_err(),
+            "instruction 2 reads a register from the previous round"
+        );
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let state = State::from_input(EXAMPLE2).unwrap();
+        assert_eq!(
+            disassemble(&state.program),
+            " 0: adv 3\n 2: out A\n 4: jnz 0\n"
+        );
+        let state = State::from_input(INPUT).unwrap();
+        assert!(disassemble(&state.program).starts_with(" 0: bst A\n 2: bxl 1\n 4: cdv B\n"));
+    }
+
+    #[test]
+    fn test_debugger() {
+        let state = State::from_input(EXAMPLE1).unwrap();
+        let mut debugger = Debugger::new(&state);
+        debugger.enable_trace();
+        debugger.add_breakpoint(4);
+        assert_eq!(debugger.run(), Stop::Breakpoint(4));
+        assert_eq!(
+            debugger.trace(),
+            [" 0: adv 1    A=364 B=0 C=0", " 2: out A    A=364 B=0 C=0"]
+        );
+        assert_eq!(debugger.output(), "4");
+        assert_eq!(debugger.run(), Stop::Breakpoint(4));
+        assert_eq!(debugger.output(), "4,6");
+        debugger.remove_breakpoint(4);
+        assert_eq!(debugger.run(), Stop::Halted);
+        assert_eq!(debugger.output(), part1(EXAMPLE1).to_string());
+
+        let mut debugger = Debugger::new(&State::from_input(INPUT).unwrap());
+        debugger.set_cycle_limit(10);
+        assert_eq!(debugger.run(), Stop::CycleLimit);
+        assert_eq!(debugger.state().ip, 4);
+
+        let input = "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 1,3,3,0\n";
+        let mut debugger = Debugger::new(&State::from_input(input).unwrap());
+        assert_eq!(debugger.run(), Stop::InfiniteLoop);
+
+        // the registers only repeat every other round
+        let input = "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 1,1,0,0,3,0\n";
+        let mut debugger = Debugger::new(&State::from_input(input).unwrap());
+        assert_eq!(debugger.run(), Stop::InfiniteLoop);
+
+        let input = "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 5,4,2,7,3,0\n";
+        let mut debugger = Debugger::new(&State::from_input(input).unwrap());
+        assert_eq!(debugger.run(), Stop::ReservedOperand(2));
+        assert_eq!(debugger.output(), "1");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = State::from_input("Register A: 1\nRegister C: 2\n").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.text, "Register C: 2");
+        let input = "Register A: 1\nRegister B: 2\nRegister C: 3\n\nProgram: 0,8,3,0\n";
+        let error = State::from_input(input).unwrap_err();
+        assert_eq!(error.line, 5);
+        assert_eq!(error.text, "8");
+        let error = State::from_input("Register A: 1\nRegister B: 2\n").unwrap_err();
+        assert_eq!(error.line, 3);
+        assert_eq!(error.text, "");
+        let input = "Register A: 1\nRegister B: 2\nRegister C: 3\n\nProgram: 0,3,5\n";
+        let error = State::from_input(input).unwrap_err();
+        assert_eq!(error.line, 5);
+        assert_eq!(error.text, "Program: 0,3,5");
+    }
+
+    #[test]
+    fn test_run_errors() {
+        let entry = Entry::new::<Day17>();
+        let input = "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 5,7\n";
+        assert_eq!(
+            entry.run(input, 1, &[]).unwrap_err(),
+            "2024 day 17: instruction 0 uses the reserved combo operand 7"
+        );
+        let input = "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 1,1,3,0\n";
+        assert_eq!(
+            entry.run(input, 1, &[]).unwrap_err(),
+            "2024 day 17: the program never halts"
+        );
+        // a jump to the last number halts
+        let input = "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 3,3,5,4\n";
+        assert_eq!(entry.run(input, 1, &[]).unwrap(), "");
+    }
 }
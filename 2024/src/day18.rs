@@ -1,6 +1,10 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::HashSet;
 use std::fmt::Display;
 
+use aoc_common::error::ParseError;
+use aoc_common::search::bfs;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 fn parse(input: &str) -> Vec<(i64, i64)> {
     input
         .lines()
@@ -12,32 +16,16 @@ fn parse(input: &str) -> Vec<(i64, i64)> {
 }
 
 fn fixed_fall(bytes: &[(i64, i64)], size: i64) -> Option<usize> {
-    let start = (0, 0);
-    let end = (size, size);
-    let mut q = VecDeque::new();
-    q.push_back((0, start));
-    let mut visited = HashSet::new();
-    while let Some((steps, pos)) = q.pop_front() {
-        if !visited.insert(pos) {
-            continue;
-        }
-        if pos == end {
-            return Some(steps);
-        }
-        let (x, y) = pos;
-        for &(dx, dy) in &[(0, 1), (0, -1), (1, 0), (-1, 0)] {
-            let nx = x + dx;
-            let ny = y + dy;
-            if !(0..=size).contains(&nx) || !(0..=size).contains(&ny) {
-                continue;
-            }
-            if bytes.contains(&(nx, ny)) {
-                continue;
-            }
-            q.push_back((steps + 1, (nx, ny)));
-        }
-    }
-    None
+    let blocked: HashSet<(i64, i64)> = bytes.iter().copied().collect();
+    let successors = |&(x, y): &(i64, i64)| {
+        [(0, 1), (0, -1), (1, 0), (-1, 0)]
+            .into_iter()
+            .map(move |(dx, dy)| (x + dx, y + dy))
+            .filter(|&(nx, ny)| (0..=size).contains(&nx) && (0..=size).contains(&ny))
+            .filter(|next| !blocked.contains(next))
+            .collect::<Vec<_>>()
+    };
+    bfs([(0, 0)], successors, |&pos| pos == (size, size)).goal_cost()
 }
 
 fn first_blocker(bytes: &[(i64, i64)], size: i64) -> (i64, i64) {
@@ -77,6 +65,27 @@ pub fn part2(input: &str) -> impl Display {
     format!("{x},{y}")
 }
 
+pub struct Day18;
+
+impl Solution for Day18 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 18;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 use std::fmt::Display;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 fn parse(input: &str) -> (Vec<&str>, Vec<&str>) {
     let (towels, designs) = input.split_once("\n\n").unwrap();
     let towels = towels.split(", ").collect();
@@ -52,6 +55,27 @@ pub fn part2(input: &str) -> impl Display {
     count
 }
 
+pub struct Day19;
+
+impl Solution for Day19 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 19;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
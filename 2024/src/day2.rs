@@ -1,5 +1,8 @@
 use std::fmt::Display;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 fn find_unsafe_pair_decreasing(levels: &[u32]) -> Option<usize> {
     (1..levels.len()).find(|&i| levels[i] >= levels[i - 1] || levels[i - 1] - levels[i] > 3)
 }
@@ -74,6 +77,27 @@ pub fn part2(input: &str) -> impl Display {
     safe_reports
 }
 
+pub struct Day2;
+
+impl Solution for Day2 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 2;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,96 +1,49 @@
-use std::collections::hash_map::Entry;
-use std::collections::{HashMap, VecDeque};
+use std::collections::HashMap;
 use std::fmt::Display;
 
-fn find(grid: &[&[u8]], c: u8) -> (isize, isize) {
-    for (i, row) in grid.iter().enumerate() {
-        for (j, &cell) in row.iter().enumerate() {
-            if cell == c {
-                return (i as isize, j as isize);
-            }
-        }
-    }
-    panic!("Did not find '{}'", c as char);
-}
+use aoc_common::error::ParseError;
+use aoc_common::grid::Grid;
+use aoc_common::search::bfs;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
-fn dijkstra(
-    grid: &[&[u8]],
-    start: (isize, isize),
-    end: (isize, isize),
-) -> HashMap<(isize, isize), isize> {
-    let mut queue = VecDeque::new();
-    queue.push_back((0, start));
-    let mut visited = HashMap::new();
-    while let Some((steps, (i, j))) = queue.pop_front() {
-        if let Entry::Vacant(entry) = visited.entry((i, j)) {
-            entry.insert(steps);
-        } else {
-            continue;
-        }
-        if (i, j) == end {
-            continue;
-        }
-        for &(i, j) in &[(i + 1, j), (i - 1, j), (i, j + 1), (i, j - 1)] {
-            if grid[i as usize][j as usize] != b'#' {
-                queue.push_back((steps + 1, (i, j)));
-            }
-        }
-    }
-    visited
-}
+type Position = (usize, usize);
 
-fn dijkstra_cheat(
-    grid: &[&[u8]],
-    start: (isize, isize),
-    max_d: isize,
-) -> HashMap<(isize, isize), isize> {
-    let rows = grid.len() as isize;
-    let cols = grid[0].len() as isize;
-    let mut queue = VecDeque::new();
-    queue.push_back((0, start));
-    let mut visited = HashMap::new();
-    while let Some((steps, (i, j))) = queue.pop_front() {
-        if steps > max_d {
-            break;
+fn steps_along_track(grid: &Grid<u8>, start: Position, end: Position) -> HashMap<Position, usize> {
+    let successors = |&pos: &Position| {
+        if pos == end {
+            return Vec::new();
         }
-        let entry = visited.entry((i, j));
-        match entry {
-            Entry::Occupied(_) => {
-                continue;
-            }
-            Entry::Vacant(entry) => {
-                entry.insert(steps);
-            }
-        }
-        for &(i, j) in &[(i + 1, j), (i - 1, j), (i, j + 1), (i, j - 1)] {
-            if (0..rows).contains(&i) && (0..cols).contains(&j) {
-                queue.push_back((steps + 1, (i, j)));
-            }
-        }
-    }
-    visited
+        grid.neighbors4(pos)
+            .filter(|&next| grid[next] != b'#')
+            .collect()
+    };
+    bfs([start], successors, |_| false).costs
 }
 
 pub fn cheat(input: &str, max_length: isize, threshold: isize) -> usize {
-    let grid: Vec<&[u8]> = input.trim().as_bytes().split(|&b| b == b'\n').collect();
-    let start = find(&grid, b'S');
-    let end = find(&grid, b'E');
-    let steps_from_start = dijkstra(&grid, start, end);
-    let steps_from_end = dijkstra(&grid, end, start);
-    let no_cheat_steps = steps_from_start[&end];
+    let grid = Grid::parse(input.trim()).unwrap();
+    let start = grid.find(&b'S').expect("Did not find 'S'");
+    let end = grid.find(&b'E').expect("Did not find 'E'");
+    let steps_from_start = steps_along_track(&grid, start, end);
+    let steps_from_end = steps_along_track(&grid, end, start);
+    let no_cheat_steps = steps_from_start[&end] as isize;
     let mut count = 0;
-    for (s, steps_f) in steps_from_start {
-        let cheats = dijkstra_cheat(&grid, s, max_length);
-        for ((i, j), steps_c) in cheats {
-            if grid[i as usize][j as usize] == b'#' {
-                continue;
-            }
-            let Some(steps_b) = steps_from_end.get(&(i, j)) else {
-                continue;
-            };
-            let gained = no_cheat_steps - (steps_f + steps_c + steps_b);
-            if gained >= threshold {
-                count += 1;
+    for (&s, &steps_f) in &steps_from_start {
+        // walls do not matter while cheating, so the shortest way is the Manhattan distance
+        for di in -max_length..=max_length {
+            let max_dj = max_length - di.abs();
+            for dj in -max_dj..=max_dj {
+                let Some(pos) = grid.offset(s, (di, dj)) else {
+                    continue;
+                };
+                let Some(&steps_b) = steps_from_end.get(&pos) else {
+                    continue;
+                };
+                let steps_c = di.abs() + dj.abs();
+                let gained = no_cheat_steps - (steps_f as isize + steps_c + steps_b as isize);
+                if gained >= threshold {
+                    count += 1;
+                }
             }
         }
     }
@@ -105,6 +58,27 @@ pub fn part2(input: &str) -> impl Display {
     cheat(input, 20, 100)
 }
 
+pub struct Day20;
+
+impl Solution for Day20 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 20;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
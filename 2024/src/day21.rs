@@ -1,175 +1,90 @@
 use std::collections::HashMap;
 use std::fmt::Display;
 
-fn num_go_and_press(start: u8, end: u8) -> &'static [&'static [u8]] {
-    // +---+---+---+
-    // | 7 | 8 | 9 |
-    // +---+---+---+
-    // | 4 | 5 | 6 |
-    // +---+---+---+
-    // | 1 | 2 | 3 |
-    // +---+---+---+
-    //     | 0 | A |
-    //     +---+---+
-    match (start, end) {
-        (b'A', b'A') => &[b"A"],
-        (b'A', b'0') => &[b"<A"],
-        (b'A', b'1') => &[b"^<<A"],
-        (b'A', b'2') => &[b"^<A", b"<^A"],
-        (b'A', b'3') => &[b"^A"],
-        (b'A', b'4') => &[b"^^<<A"],
-        (b'A', b'5') => &[b"^^<A", b"<^^A"],
-        (b'A', b'6') => &[b"^^A"],
-        (b'A', b'7') => &[b"^^^<<A"],
-        (b'A', b'8') => &[b"^^^<A", b"<^^^A"],
-        (b'A', b'9') => &[b"^^^A"],
-        (b'0', b'A') => &[b">A"],
-        (b'0', b'0') => &[b"A"],
-        (b'0', b'1') => &[b"^<A"],
-        (b'0', b'2') => &[b"^A"],
-        (b'0', b'3') => &[b"^>A", b">^A"],
-        (b'0', b'4') => &[b"^^<A"],
-        (b'0', b'5') => &[b"^^A"],
-        (b'0', b'6') => &[b"^^>A", b">^^A"],
-        (b'0', b'7') => &[b"^^^<A"],
-        (b'0', b'8') => &[b"^^^A"],
-        (b'0', b'9') => &[b"^^^>A", b">^^^A"],
-        (b'1', b'A') => &[b">>vA"],
-        (b'1', b'0') => &[b">vA"],
-        (b'1', b'1') => &[b"A"],
-        (b'1', b'2') => &[b">A"],
-        (b'1', b'3') => &[b">>A"],
-        (b'1', b'4') => &[b"^A"],
-        (b'1', b'5') => &[b">^A", b"^>A"],
-        (b'1', b'6') => &[b"^>>A", b">>^A"],
-        (b'1', b'7') => &[b"^^A"],
-        (b'1', b'8') => &[b"^^>A", b">^^A"],
-        (b'1', b'9') => &[b"^^>>A", b">>^^A"],
-        (b'2', b'A') => &[b">vA", b"v>A"],
-        (b'2', b'0') => &[b"vA"],
-        (b'2', b'1') => &[b"<A"],
-        (b'2', b'2') => &[b"A"],
-        (b'2', b'3') => &[b">A"],
-        (b'2', b'4') => &[b"^<A", b"<^A"],
-        (b'2', b'5') => &[b"^A"],
-        (b'2', b'6') => &[b">^A", b"^>A"],
-        (b'2', b'7') => &[b"^^<A", b"<^^A"],
-        (b'2', b'8') => &[b"^^A"],
-        (b'2', b'9') => &[b"^^>A", b">^^A"],
-        (b'3', b'A') => &[b"vA"],
-        (b'3', b'0') => &[b"v<A", b"<vA"],
-        (b'3', b'1') => &[b"<<A"],
-        (b'3', b'2') => &[b"<A"],
-        (b'3', b'3') => &[b"A"],
-        (b'3', b'4') => &[b"^<<A", b"<<^A"],
-        (b'3', b'5') => &[b"^<A", b"<^A"],
-        (b'3', b'6') => &[b"^A"],
-        (b'3', b'7') => &[b"<<^^A", b"^^<<A"],
-        (b'3', b'8') => &[b"^^<A", b"<^^A"],
-        (b'3', b'9') => &[b"^^A"],
-        (b'4', b'A') => &[b">>vvA"],
-        (b'4', b'0') => &[b">vvA"],
-        (b'4', b'1') => &[b"vA"],
-        (b'4', b'2') => &[b">vA", b"v>A"],
-        (b'4', b'3') => &[b">>vA", b"v>>A"],
-        (b'4', b'4') => &[b"A"],
-        (b'4', b'5') => &[b">A"],
-        (b'4', b'6') => &[b">>A"],
-        (b'4', b'7') => &[b"^A"],
-        (b'4', b'8') => &[b">^A", b"^>A"],
-        (b'4', b'9') => &[b">>^A", b"^>>A"],
-        (b'5', b'A') => &[b">vvA", b"vv>A"],
-        (b'5', b'0') => &[b"vvA"],
-        (b'5', b'1') => &[b"v<A", b"<vA"],
-        (b'5', b'2') => &[b"vA"],
-        (b'5', b'3') => &[b">vA", b"v>A"],
-        (b'5', b'4') => &[b"<A"],
-        (b'5', b'5') => &[b"A"],
-        (b'5', b'6') => &[b">A"],
-        (b'5', b'7') => &[b"^<A", b"<^A"],
-        (b'5', b'8') => &[b"^A"],
-        (b'5', b'9') => &[b">^A", b"^>A"],
-        (b'6', b'A') => &[b"vvA"],
-        (b'6', b'0') => &[b"vv<A", b"<vvA"],
-        (b'6', b'1') => &[b"v<<A", b"<<vA"],
-        (b'6', b'2') => &[b"v<A", b"<vA"],
-        (b'6', b'3') => &[b"vA"],
-        (b'6', b'4') => &[b"<<A"],
-        (b'6', b'5') => &[b"<A"],
-        (b'6', b'6') => &[b"A"],
-        (b'6', b'7') => &[b"^<<A", b"<<^A"],
-        (b'6', b'8') => &[b"^<A", b"<^A"],
-        (b'6', b'9') => &[b"^A"],
-        (b'7', b'A') => &[b">>vvvA"],
-        (b'7', b'0') => &[b">vvvA"],
-        (b'7', b'1') => &[b"vvA"],
-        (b'7', b'2') => &[b">vvA", b"vv>A"],
-        (b'7', b'3') => &[b">>vvA", b"vv>>A"],
-        (b'7', b'4') => &[b"vA"],
-        (b'7', b'5') => &[b">vA", b"v>A"],
-        (b'7', b'6') => &[b">>vA", b"v>>A"],
-        (b'7', b'7') => &[b"A"],
-        (b'7', b'8') => &[b">A"],
-        (b'7', b'9') => &[b">>A"],
-        (b'8', b'A') => &[b">vvvA", b"vvv>A"],
-        (b'8', b'0') => &[b"vvvA"],
-        (b'8', b'1') => &[b"<vvA", b"vv<A"],
-        (b'8', b'2') => &[b"vvA"],
-        (b'8', b'3') => &[b">vvA", b"vv>A"],
-        (b'8', b'4') => &[b"<vA", b"v<A"],
-        (b'8', b'5') => &[b"vA"],
-        (b'8', b'6') => &[b">vA", b"v>A"],
-        (b'8', b'7') => &[b"<A"],
-        (b'8', b'8') => &[b"A"],
-        (b'8', b'9') => &[b">A"],
-        (b'9', b'A') => &[b"vvvA"],
-        (b'9', b'0') => &[b"vvv<A", b"<vvvA"],
-        (b'9', b'1') => &[b"vv<<A", b"<<vvA"],
-        (b'9', b'2') => &[b"vv<A", b"<vvA"],
-        (b'9', b'3') => &[b"vvA"],
-        (b'9', b'4') => &[b"<<vA", b"v<<A"],
-        (b'9', b'5') => &[b"<vA", b"v<A"],
-        (b'9', b'6') => &[b"vA"],
-        (b'9', b'7') => &[b"<<A"],
-        (b'9', b'8') => &[b"<A"],
-        (b'9', b'9') => &[b"A"],
-        _ => unreachable!(),
+use aoc_common::error::ParseError;
+use aoc_common::grid::Grid;
+use aoc_common::search::bfs;
+use aoc_common::solution::{Answer, Parameters, Solution};
+use aoc_common::trace;
+
+// +---+---+---+
+// | 7 | 8 | 9 |
+// +---+---+---+
+// | 4 | 5 | 6 |
+// +---+---+---+
+// | 1 | 2 | 3 |
+// +---+---+---+
+//     | 0 | A |
+//     +---+---+
+const NUMERIC_KEYPAD: &str = "789\n456\n123\n 0A\n";
+
+//     +---+---+
+//     | ^ | A |
+// +---+---+---+
+// | < | v | > |
+// +---+---+---+
+const DIRECTIONAL_KEYPAD: &str = " ^A\n<v>\n";
+
+/// Buttons laid out on a grid, where a space is a gap the robot arm must not go over
+struct Keypad {
+    keys: Vec<u8>,
+    /// Candidate shortest sequences of directions to move from a key to another and press it
+    moves: HashMap<(u8, u8), Vec<Vec<u8>>>,
+}
+
+impl Keypad {
+    fn new(layout: &str) -> Result<Self, String> {
+        let grid = Grid::parse(layout).map_err(|error| error.to_string())?;
+        let keys: Vec<(u8, (usize, usize))> = grid
+            .iter()
+            .filter(|&(_, &key)| key != b' ')
+            .map(|(pos, &key)| (key, pos))
+            .collect();
+        let successors = |&pos: &(usize, usize)| -> Vec<(usize, usize)> {
+            grid.neighbors4(pos).filter(|&n| grid[n] != b' ').collect()
+        };
+        let mut moves = HashMap::new();
+        for &(start, start_pos) in &keys {
+            let search = bfs([start_pos], successors, |_| false);
+            for &(end, end_pos) in &keys {
+                // the cheapest path for the robot above is not always one with the fewest turns
+                let mut candidates: Vec<Vec<u8>> = search
+                    .all_paths(&end_pos)
+                    .iter()
+                    .map(|path| {
+                        let mut directions: Vec<u8> = path
+                            .windows(2)
+                            .map(|step| direction(step[0], step[1]))
+                            .collect();
+                        directions.push(b'A');
+                        directions
+                    })
+                    .collect();
+                if candidates.is_empty() {
+                    return Err(format!(
+                        "no way from {} to {} around the gaps",
+                        start as char, end as char
+                    ));
+                }
+                candidates.sort();
+                moves.insert((start, end), candidates);
+            }
+        }
+        let keys = keys.into_iter().map(|(key, _)| key).collect();
+        Ok(Keypad { keys, moves })
     }
 }
 
-fn dir_go_and_press(start: u8, end: u8) -> &'static [&'static [u8]] {
-    //     +---+---+
-    //     | ^ | A |
-    // +---+---+---+
-    // | < | v | > |
-    // +---+---+---+
-    match (start, end) {
-        (b'A', b'A') => &[b"A"],
-        (b'A', b'<') => &[b"v<<A"],
-        (b'A', b'>') => &[b"vA"],
-        (b'A', b'^') => &[b"<A"],
-        (b'A', b'v') => &[b"v<A", b"<vA"],
-        (b'<', b'A') => &[b">>^A"],
-        (b'<', b'<') => &[b"A"],
-        (b'<', b'>') => &[b">>A"],
-        (b'<', b'^') => &[b">^A"],
-        (b'<', b'v') => &[b">A"],
-        (b'>', b'A') => &[b"^A"],
-        (b'>', b'<') => &[b"<<A"],
-        (b'>', b'>') => &[b"A"],
-        (b'>', b'^') => &[b"<^A", b"^<A"],
-        (b'>', b'v') => &[b"<A"],
-        (b'^', b'A') => &[b">A"],
-        (b'^', b'<') => &[b"v<A"],
-        (b'^', b'>') => &[b"v>A", b">vA"],
-        (b'^', b'^') => &[b"A"],
-        (b'^', b'v') => &[b"vA"],
-        (b'v', b'A') => &[b"^>A", b">^A"],
-        (b'v', b'<') => &[b"<A"],
-        (b'v', b'>') => &[b">A"],
-        (b'v', b'^') => &[b"^A"],
-        (b'v', b'v') => &[b"A"],
-        _ => unreachable!(),
+/// Button moving the arm from `from` to the adjacent `to`
+fn direction(from: (usize, usize), to: (usize, usize)) -> u8 {
+    if to.0 < from.0 {
+        b'^'
+    } else if to.0 > from.0 {
+        b'v'
+    } else if to.1 < from.1 {
+        b'<'
+    } else {
+        b'>'
     }
 }
 
@@ -177,55 +92,45 @@ fn path_cost(costs: &HashMap<(u8, u8), usize>, path: &[u8]) -> usize {
     let mut c = b'A';
     let mut cost = 0;
     for &x in path {
-        println!("{} -> {}", c as char, x as char);
+        trace!("{} -> {}", c as char, x as char);
         cost += costs[&(c, x)];
         c = x;
     }
     cost
 }
 
-pub fn type_on_keypads(line: &str, n_robots: usize) -> usize {
+/// Number of button presses to type `code` on the last keypad of the chain; each keypad is
+/// operated by a robot controlled from the previous one, and the first one is used directly
+fn presses_through_chain(chain: &[&Keypad], code: &[u8]) -> usize {
+    let (first, robots) = chain.split_first().unwrap();
     // cost to move from button X to button Y and press button Y
     let mut costs: HashMap<(u8, u8), usize> = HashMap::new();
-    // top-level directional keypad (cost of moving is 0)
-    for &start in b"A<>^v" {
-        for &end in b"A<>^v" {
+    for &start in &first.keys {
+        for &end in &first.keys {
             costs.insert((start, end), 1);
         }
     }
-    // intermediate directional keypads
-    for _ in 0..n_robots {
+    for keypad in robots {
         let mut new_costs: HashMap<(u8, u8), usize> = HashMap::new();
-        for &start in b"A<>^v" {
-            for &end in b"A<>^v" {
-                new_costs.insert(
-                    (start, end),
-                    dir_go_and_press(start, end)
-                        .iter()
-                        .map(|&path| path_cost(&costs, path))
-                        .min()
-                        .unwrap(),
-                );
-            }
+        for (&(start, end), candidates) in &keypad.moves {
+            let cost = candidates
+                .iter()
+                .map(|path| path_cost(&costs, path))
+                .min()
+                .unwrap();
+            new_costs.insert((start, end), cost);
         }
         costs = new_costs;
     }
-    // numeric keypad
-    let mut new_costs: HashMap<(u8, u8), usize> = HashMap::new();
-    for &start in b"A0123456789" {
-        for &end in b"A0123456789" {
-            new_costs.insert(
-                (start, end),
-                num_go_and_press(start, end)
-                    .iter()
-                    .map(|&path| path_cost(&costs, path))
-                    .min()
-                    .unwrap(),
-            );
-        }
-    }
-    costs = new_costs;
-    path_cost(&costs, line.as_bytes())
+    path_cost(&costs, code)
+}
+
+pub fn type_on_keypads(line: &str, n_robots: usize) -> usize {
+    let numeric = Keypad::new(NUMERIC_KEYPAD).unwrap();
+    let directional = Keypad::new(DIRECTIONAL_KEYPAD).unwrap();
+    let mut chain = vec![&directional; n_robots + 1];
+    chain.push(&numeric);
+    presses_through_chain(&chain, line.as_bytes())
 }
 
 pub fn part1(input: &str) -> impl Display {
@@ -234,7 +139,7 @@ pub fn part1(input: &str) -> impl Display {
         let length = type_on_keypads(line, 2);
         let numeric = line.strip_suffix("A").unwrap();
         let value: usize = numeric.parse().unwrap();
-        println!("{length} * {value} = {}", length * value);
+        trace!("{line}: {length} * {value} = {}", length * value);
         total += length * value;
     }
     total
@@ -251,6 +156,27 @@ pub fn part2(input: &str) -> impl Display {
     total
 }
 
+pub struct Day21;
+
+impl Solution for Day21 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 21;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -264,6 +190,35 @@ mod tests {
         assert_eq!(part1(INPUT).to_string(), "162740");
     }
 
+    #[test]
+    fn test_keypads() {
+        let numeric = Keypad::new(NUMERIC_KEYPAD).unwrap();
+        assert_eq!(numeric.keys.len(), 11);
+        assert_eq!(numeric.moves[&(b'A', b'1')], [b"<^<A", b"^<<A"]);
+        let candidates = &numeric.moves[&(b'9', b'1')];
+        assert_eq!(candidates.len(), 6);
+        assert_eq!(
+            (&candidates[0], &candidates[5]),
+            (&b"<<vvA".to_vec(), &b"vv<<A".to_vec())
+        );
+        let directional = Keypad::new(DIRECTIONAL_KEYPAD).unwrap();
+        assert_eq!(directional.moves[&(b'<', b'A')], [b">>^A", b">^>A"]);
+        assert_eq!(directional.moves[&(b'v', b'v')], [b"A"]);
+
+        // a single robot, without the numeric keypad: v<<A then >>^A
+        assert_eq!(
+            presses_through_chain(&[&directional, &directional], b"<A"),
+            8
+        );
+
+        // the gap blocks both L-shaped paths from 1 to 2, and from A to 1 going up first
+        let keypad = Keypad::new("1 2\n34A\n").unwrap();
+        assert_eq!(keypad.moves[&(b'1', b'2')], [b"v>>^A"]);
+        assert_eq!(keypad.moves[&(b'A', b'1')], [b"<<^A"]);
+        assert_eq!(presses_through_chain(&[&directional, &keypad], b"12"), 9);
+        assert!(Keypad::new("1 2\n").is_err());
+    }
+
     #[test]
     fn test_part2() {
         assert_eq!(part2(INPUT).to_string(), "203640915832208");
@@ -1,5 +1,8 @@
 use std::fmt::Display;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 fn next_secret(mut secret: i64) -> i64 {
     secret = ((secret * 64) ^ secret) % 16777216;
     secret = ((secret / 32) ^ secret) % 16777216;
@@ -69,6 +72,27 @@ pub fn part2(input: &str) -> impl Display {
     bananas_per_changes.into_iter().max().unwrap()
 }
 
+pub struct Day22;
+
+impl Solution for Day22 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 22;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,6 +2,9 @@ use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 use std::iter::once;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 fn parse(input: &str) -> (HashSet<&str>, HashSet<(&str, &str)>) {
     let mut computers = HashSet::new();
     let mut links = HashSet::new();
@@ -86,6 +89,27 @@ pub fn part2(input: &str) -> impl Display {
     v.join(",")
 }
 
+pub struct Day23;
+
+impl Solution for Day23 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 23;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,11 +1,13 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 
-fn eval<'a>(
-    values: &'_ mut HashMap<&'a str, bool>,
-    gates: &'a HashMap<&'a str, (&'a str, &'a str, &'a str)>,
-    output: &'a str,
-) -> bool {
+use aoc_common::dot::Dot;
+use aoc_common::error::{ParseError, SolveError};
+use aoc_common::solution::{Answer, Parameters, Solution};
+
+type Gates<'a> = HashMap<&'a str, (&'a str, &'a str, &'a str)>;
+
+fn eval<'a>(values: &'_ mut HashMap<&'a str, bool>, gates: &Gates<'a>, output: &'a str) -> bool {
     if let Some(&value) = values.get(output) {
         return value;
     }
@@ -22,9 +24,9 @@ fn eval<'a>(
     value
 }
 
-pub fn part1(input: &str) -> impl Display {
+fn parse(input: &str) -> (HashMap<&str, bool>, Gates<'_>) {
     let (initial_values, gates) = input.trim().split_once("\n\n").unwrap();
-    let mut values: HashMap<&str, bool> = initial_values
+    let values = initial_values
         .lines()
         .map(|line| {
             let (name, value) = line.split_once(": ").unwrap();
@@ -32,7 +34,7 @@ pub fn part1(input: &str) -> impl Display {
             (name, value)
         })
         .collect();
-    let gates: HashMap<&str, (&str, &str, &str)> = gates
+    let gates = gates
         .lines()
         .map(|line| {
             let (gate, res) = line.split_once(" -> ").unwrap();
@@ -41,15 +43,209 @@ pub fn part1(input: &str) -> impl Display {
             (res, (lhs, op, rhs))
         })
         .collect();
-    let mut outputs: Vec<&str> = gates
-        .keys()
-        .copied()
-        .filter(|&key| key.starts_with('z'))
+    (values, gates)
+}
+
+fn wires<'a>(gates: &Gates<'a>, prefix: char) -> Vec<&'a str> {
+    let mut wires: Vec<&str> = gates
+        .iter()
+        .flat_map(|(&output, &(lhs, _, rhs))| [output, lhs, rhs])
+        .filter(|wire| wire.starts_with(prefix))
+        .collect();
+    wires.sort();
+    wires.dedup();
+    wires
+}
+
+/// Read the number made of the `z` wires, or `None` if the gates form a loop
+fn add(gates: &Gates, x: u64, y: u64) -> Option<u64> {
+    fn eval_checked<'a>(
+        values: &mut HashMap<&'a str, bool>,
+        visiting: &mut HashSet<&'a str>,
+        gates: &Gates<'a>,
+        output: &'a str,
+    ) -> Option<bool> {
+        if let Some(&value) = values.get(output) {
+            return Some(value);
+        }
+        if !visiting.insert(output) {
+            return None;
+        }
+        let &(lhs, op, rhs) = gates.get(output)?;
+        let lhs = eval_checked(values, visiting, gates, lhs)?;
+        let rhs = eval_checked(values, visiting, gates, rhs)?;
+        let value = match op {
+            "AND" => lhs & rhs,
+            "OR" => lhs | rhs,
+            _ => lhs ^ rhs,
+        };
+        values.insert(output, value);
+        Some(value)
+    }
+
+    let mut values = HashMap::new();
+    for (i, wire) in wires(gates, 'x').into_iter().enumerate() {
+        values.insert(wire, x >> i & 1 == 1);
+    }
+    for (i, wire) in wires(gates, 'y').into_iter().enumerate() {
+        values.insert(wire, y >> i & 1 == 1);
+    }
+    let mut visiting = HashSet::new();
+    let mut res = 0;
+    for output in wires(gates, 'z').into_iter().rev() {
+        res <<= 1;
+        if eval_checked(&mut values, &mut visiting, gates, output)? {
+            res |= 1;
+        }
+    }
+    Some(res)
+}
+
+/// Outputs of the gates that do not fit in a ripple-carry adder
+///
+/// Bit 0 is a half adder (`z00 = x00 ^ y00`, carry `x00 & y00`), the other bits are full adders
+/// (`zN = (xN ^ yN) ^ carry`, next carry `(xN & yN) | ((xN ^ yN) & carry)`), and the last `z` wire
+/// is the final carry.
+fn misplaced_outputs<'a>(gates: &Gates<'a>) -> Vec<&'a str> {
+    let mut consumers: HashMap<&str, Vec<&str>> = HashMap::new();
+    for &(lhs, op, rhs) in gates.values() {
+        consumers.entry(lhs).or_default().push(op);
+        consumers.entry(rhs).or_default().push(op);
+    }
+    let feeds = |output: &str, op: &str| consumers.get(output).is_some_and(|ops| ops.contains(&op));
+    let last_z = wires(gates, 'z').pop().unwrap_or_default();
+    let mut misplaced: Vec<&str> = gates
+        .iter()
+        .filter(|&(&output, &(lhs, op, rhs))| {
+            let from_inputs = [lhs, rhs]
+                .iter()
+                .all(|wire| wire.starts_with('x') || wire.starts_with('y'));
+            let first_bit = from_inputs && lhs.ends_with("00");
+            if output == last_z {
+                return op != "OR";
+            }
+            if output.starts_with('z') {
+                // only the first bit comes straight from the inputs
+                return op != "XOR" || from_inputs != (output == "z00");
+            }
+            match op {
+                "XOR" => !from_inputs || !feeds(output, "XOR"),
+                "AND" => !first_bit && !feeds(output, "OR"),
+                _ => feeds(output, "OR"),
+            }
+        })
+        .map(|(&output, _)| output)
         .collect();
+    misplaced.sort();
+    misplaced
+}
+
+fn swap_outputs(gates: &mut Gates, a: &str, b: &str) {
+    let gate_a = gates[a];
+    let gate_b = gates[b];
+    *gates.get_mut(a).unwrap() = gate_b;
+    *gates.get_mut(b).unwrap() = gate_a;
+}
+
+fn adds_correctly(gates: &Gates, bits: usize) -> bool {
+    let mask = (1 << bits) - 1;
+    let mut cases = vec![(mask, 1), (mask, mask)];
+    for i in 0..bits {
+        cases.push((1 << i, 0));
+        cases.push((0, 1 << i));
+        cases.push((1 << i, 1 << i));
+    }
+    cases
+        .into_iter()
+        .all(|(x, y)| add(gates, x, y) == Some(x + y))
+}
+
+/// Pair up the candidates so that swapping the outputs of each pair repairs the adder
+fn find_swaps<'a>(
+    gates: &mut Gates<'a>,
+    bits: usize,
+    candidates: &[&'a str],
+    swaps: &mut Vec<(&'a str, &'a str)>,
+) -> bool {
+    let Some((&first, rest)) = candidates.split_first() else {
+        return misplaced_outputs(gates).is_empty() && adds_correctly(gates, bits);
+    };
+    for (i, &other) in rest.iter().enumerate() {
+        swap_outputs(gates, first, other);
+        swaps.push((first, other));
+        let remaining: Vec<&str> = rest[..i].iter().chain(&rest[i + 1..]).copied().collect();
+        if find_swaps(gates, bits, &remaining, swaps) {
+            return true;
+        }
+        swaps.pop();
+        swap_outputs(gates, first, other);
+    }
+    false
+}
+
+fn repair_adder<'a>(gates: &Gates<'a>) -> Result<Vec<(&'a str, &'a str)>, String> {
+    let bits = wires(gates, 'x').len();
+    if bits == 0 {
+        return Err("no x wires".to_string());
+    }
+    if wires(gates, 'y').len() != bits || wires(gates, 'z').len() != bits + 1 {
+        return Err(format!("expected {bits} y wires and {} z wires", bits + 1));
+    }
+    if gates.len() != 5 * bits - 3 {
+        return Err(format!(
+            "a ripple-carry adder on {bits} bits has {} gates, not {}",
+            5 * bits - 3,
+            gates.len()
+        ));
+    }
+    let candidates = misplaced_outputs(gates);
+    let mut gates = gates.clone();
+    let mut swaps = Vec::new();
+    if !candidates.len().is_multiple_of(2) || !find_swaps(&mut gates, bits, &candidates, &mut swaps)
+    {
+        return Err(format!(
+            "could not repair the adder by swapping the outputs of {}",
+            candidates.join(",")
+        ));
+    }
+    Ok(swaps)
+}
+
+fn swapped_wires(gates: &Gates) -> Result<String, String> {
+    let swaps = repair_adder(gates)?;
+    let mut wires: Vec<&str> = swaps.into_iter().flat_map(|(a, b)| [a, b]).collect();
+    wires.sort();
+    Ok(wires.join(","))
+}
+
+/// Wires are nodes, and each gate is drawn as the edges from its inputs to its output
+fn gates_to_dot(gates: &Gates) -> String {
+    let mut outputs: Vec<&str> = gates.keys().copied().collect();
     outputs.sort();
-    outputs.reverse();
-    let mut res = 0u64;
+    let mut dot = Dot::digraph();
     for output in outputs {
+        let (lhs, op, rhs) = gates[output];
+        dot.edge(lhs, output, &[("label", op)]);
+        dot.edge(rhs, output, &[("label", op)]);
+    }
+    dot.to_string()
+}
+
+/// Render the gates of the input, optionally after repairing the adder
+pub fn to_dot(input: &str, repaired: bool) -> Result<String, String> {
+    let (_, mut gates) = parse(input);
+    if repaired {
+        for (a, b) in repair_adder(&gates.clone())? {
+            swap_outputs(&mut gates, a, b);
+        }
+    }
+    Ok(gates_to_dot(&gates))
+}
+
+pub fn part1(input: &str) -> impl Display {
+    let (mut values, gates) = parse(input);
+    let mut res = 0u64;
+    for output in wires(&gates, 'z').into_iter().rev() {
         res <<= 1;
         if eval(&mut values, &gates, output) {
             res |= 1;
@@ -58,8 +254,37 @@ pub fn part1(input: &str) -> impl Display {
     res
 }
 
+pub fn part2(input: &str) -> impl Display {
+    let (_, gates) = parse(input);
+    swapped_wires(&gates).unwrap()
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 24;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        let (_, gates) = parse(input);
+        Some(swapped_wires(&gates).map_err(SolveError::from))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use aoc_common::solution::Entry;
+
     use super::*;
 
     const EXAMPLE1: &str = include_str!("../examples/day24-1.txt");
@@ -74,9 +299,46 @@ mod tests {
         assert_eq!(part1(INPUT).to_string(), "58740594706150");
     }
 
+    #[test]
+    fn test_to_dot() {
+        let dot = to_dot(EXAMPLE1, false).unwrap();
+        assert!(dot.starts_with("digraph {\n    \"x00\" -> \"z00\" [label=\"AND\"];\n"));
+        assert!(to_dot(EXAMPLE1, true).is_err());
+
+        // before and after the repair, the outputs of the swapped gates differ
+        let orig = to_dot(INPUT, false).unwrap();
+        let fixed = to_dot(INPUT, true).unwrap();
+        assert!(orig.contains("    \"tck\" -> \"z14\" [label=\"OR\"];\n"));
+        assert!(!fixed.contains("-> \"z14\" [label=\"OR\"]"));
+        assert_eq!(fixed.lines().count(), orig.lines().count());
+    }
+
     #[test]
     fn test_part2() {
-        // Done by hand, see day24-orig.dot and day24-fixed.dot
-        // cvh,dbb,hbk,kvn,tfn,z14,z18,z23
+        assert_eq!(part2(INPUT).to_string(), "cvh,dbb,hbk,kvn,tfn,z14,z18,z23");
+        let (_, gates) = parse(EXAMPLE1);
+        assert_eq!(
+            swapped_wires(&gates).unwrap_err(),
+            "expected 3 y wires and 4 z wires"
+        );
+        assert_eq!(repair_adder(&Gates::new()).unwrap_err(), "no x wires");
+        // through the runner, the error is reported instead of panicking
+        let entry = Entry::new::<Day24>();
+        assert_eq!(
+            entry.run(EXAMPLE1, 2, &[]).unwrap_err(),
+            "2024 day 24: expected 3 y wires and 4 z wires"
+        );
+    }
+
+    #[test]
+    fn test_swap_with_input_gate() {
+        // the output of `x05 XOR y05` sent straight to z05
+        let (_, mut gates) = parse(INPUT);
+        for (a, b) in repair_adder(&gates.clone()).unwrap() {
+            swap_outputs(&mut gates, a, b);
+        }
+        swap_outputs(&mut gates, "z05", "wrk");
+        assert_eq!(misplaced_outputs(&gates), ["wrk", "z05"]);
+        assert_eq!(repair_adder(&gates).unwrap(), [("wrk", "z05")]);
     }
 }
@@ -1,5 +1,8 @@
 use std::fmt::Display;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 pub fn part1(input: &str) -> impl Display {
     let mut locks = Vec::new();
     let mut keys = Vec::new();
@@ -33,6 +36,23 @@ pub fn part1(input: &str) -> impl Display {
     fit
 }
 
+pub struct Day25;
+
+impl Solution for Day25 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 25;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,5 +1,8 @@
 use std::fmt::Display;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 fn parse_number(s: &[u8]) -> u64 {
     s.iter().fold(0, |acc, c| acc * 10 + (*c - b'0') as u64)
 }
@@ -113,6 +116,27 @@ pub fn part2(input: &str) -> impl Display {
     total
 }
 
+pub struct Day3;
+
+impl Solution for Day3 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 3;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
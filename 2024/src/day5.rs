@@ -2,6 +2,9 @@ use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::fmt::Display;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 fn parse(
     input: &str,
 ) -> (
@@ -85,6 +88,27 @@ pub fn part2(input: &str) -> impl Display {
     count
 }
 
+pub struct Day5;
+
+impl Solution for Day5 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 5;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,9 @@
 use std::collections::HashSet;
 use std::fmt::Display;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 fn find_start(grid: &[&[u8]]) -> (i64, i64) {
     for (i, row) in grid.iter().enumerate() {
         for (j, &c) in row.iter().enumerate() {
@@ -111,6 +114,27 @@ pub fn part2(input: &str) -> impl Display {
     count_loops(&grid)
 }
 
+pub struct Day6;
+
+impl Solution for Day6 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 6;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
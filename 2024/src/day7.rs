@@ -1,5 +1,8 @@
 use std::fmt::Display;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 pub fn part1(input: &str) -> impl Display {
     let mut total = 0;
     for line in input.lines() {
@@ -52,6 +55,27 @@ pub fn part2(input: &str) -> impl Display {
     total
 }
 
+pub struct Day7;
+
+impl Solution for Day7 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 7;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
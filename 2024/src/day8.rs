@@ -1,6 +1,9 @@
 use std::collections::HashSet;
 use std::fmt::Display;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 pub fn part1(input: &str) -> impl Display {
     let mut frequencies: HashSet<char> = input.chars().collect();
     frequencies.remove(&'.');
@@ -87,6 +90,27 @@ pub fn part2(input: &str) -> impl Display {
     antinodes.len()
 }
 
+pub struct Day8;
+
+impl Solution for Day8 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 8;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,5 +1,8 @@
 use std::fmt::Display;
 
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 fn file_hash(position: u64, file_index: u64, file_size: u64) -> u64 {
     // a1 = position * file_index
     // an = (position + file_size - 1) * file_index
@@ -120,6 +123,27 @@ pub fn part2(input: &str) -> impl Display {
     res
 }
 
+pub struct Day9;
+
+impl Solution for Day9 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 9;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
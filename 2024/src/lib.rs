@@ -1,3 +1,5 @@
+use aoc_common::solution::Registry;
+
 pub mod day1;
 pub mod day10;
 pub mod day11;
@@ -23,3 +25,31 @@ pub mod day6;
 pub mod day7;
 pub mod day8;
 pub mod day9;
+
+pub fn register(registry: &mut Registry) {
+    registry.register::<day1::Day1>();
+    registry.register::<day2::Day2>();
+    registry.register::<day3::Day3>();
+    registry.register::<day4::Day4>();
+    registry.register::<day5::Day5>();
+    registry.register::<day6::Day6>();
+    registry.register::<day7::Day7>();
+    registry.register::<day8::Day8>();
+    registry.register::<day9::Day9>();
+    registry.register::<day10::Day10>();
+    registry.register::<day11::Day11>();
+    registry.register::<day12::Day12>();
+    registry.register::<day13::Day13>();
+    registry.register::<day14::Day14>();
+    registry.register::<day15::Day15>();
+    registry.register::<day16::Day16>();
+    registry.register::<day17::Day17>();
+    registry.register::<day18::Day18>();
+    registry.register::<day19::Day19>();
+    registry.register::<day20::Day20>();
+    registry.register::<day21::Day21>();
+    registry.register::<day22::Day22>();
+    registry.register::<day23::Day23>();
+    registry.register::<day24::Day24>();
+    registry.register::<day25::Day25>();
+}
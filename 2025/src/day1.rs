@@ -1,4 +1,9 @@
-fn part1(input: &str) -> i32 {
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
+pub fn part1(input: &str) -> i32 {
     let mut pos = 50;
     let mut count = 0;
     for line in input.lines() {
@@ -17,7 +22,7 @@ fn part1(input: &str) -> i32 {
     count
 }
 
-fn part2(input: &str) -> i32 {
+pub fn part2(input: &str) -> i32 {
     let mut pos = 50;
     let mut count = 0;
     for line in input.lines() {
@@ -47,6 +52,27 @@ fn part2(input: &str) -> i32 {
     count
 }
 
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u32 = 2025;
+    const DAY: u32 = 1;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,9 @@
-fn part1(input: &str) -> u64 {
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
+pub fn part1(input: &str) -> u64 {
     let mut invalid = 0;
     for range in input.trim().split(',') {
         let (start, end) = range.split_once('-').unwrap();
@@ -15,7 +20,7 @@ fn part1(input: &str) -> u64 {
     invalid
 }
 
-fn part2(input: &str) -> u64 {
+pub fn part2(input: &str) -> u64 {
     let mut invalid = 0;
     for range in input.trim().split(',') {
         let (start, end) = range.split_once('-').unwrap();
@@ -42,6 +47,27 @@ fn part2(input: &str) -> u64 {
     invalid
 }
 
+pub struct Day2;
+
+impl Solution for Day2 {
+    const YEAR: u32 = 2025;
+    const DAY: u32 = 2;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
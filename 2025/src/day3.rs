@@ -1,3 +1,8 @@
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
 fn solve(input: &str, digits: usize) -> u64 {
     let mut ret = 0u64;
     for line in input.lines() {
@@ -23,15 +28,36 @@ fn solve(input: &str, digits: usize) -> u64 {
     ret
 }
 
-fn part1(input: &str) -> u64 {
+pub fn part1(input: &str) -> u64 {
     solve(input, 2)
 }
 
-fn part2(input: &str) -> u64 {
+pub fn part2(input: &str) -> u64 {
     solve(input, 12)
 }
 
 
+pub struct Day3;
+
+impl Solution for Day3 {
+    const YEAR: u32 = 2025;
+    const DAY: u32 = 3;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
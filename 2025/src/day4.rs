@@ -1,4 +1,8 @@
 use std::collections::HashSet;
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
 
 type Roll = (isize, isize);
 type RollSet = HashSet<Roll>;
@@ -37,12 +41,12 @@ fn removable(roll_set: &RollSet) -> Vec<Roll> {
     ret
 }
 
-fn part1(input: &str) -> usize {
+pub fn part1(input: &str) -> usize {
     let roll_set = parse_roll_set(input);
     removable(&roll_set).len()
 }
 
-fn part2(input: &str) -> usize {
+pub fn part2(input: &str) -> usize {
     let mut roll_set = parse_roll_set(input);
     let mut ret = 0;
     loop {
@@ -59,6 +63,27 @@ fn part2(input: &str) -> usize {
 }
 
 
+pub struct Day4;
+
+impl Solution for Day4 {
+    const YEAR: u32 = 2025;
+    const DAY: u32 = 4;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,9 @@
-fn part1(input: &str) -> u64 {
+use std::fmt::Display;
+
+use aoc_common::error::ParseError;
+use aoc_common::solution::{Answer, Parameters, Solution};
+
+pub fn part1(input: &str) -> u64 {
     let lines: Vec<&str> = input.lines().collect();
     let numbers_lines: Vec<Vec<u64>> = lines[0..lines.len() - 1].iter().map(|line| line.split_whitespace().map(|n| n.parse().unwrap()).collect()).collect();
     let ops: Vec<&str> = lines[lines.len() - 1].split_whitespace().collect();
@@ -17,7 +22,7 @@ fn part1(input: &str) -> u64 {
     ret
 }
 
-fn part2(input: &str) -> u64 {
+pub fn part2(input: &str) -> u64 {
     let grid: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
     let rows = grid.len();
     let cols = grid[0].len();
@@ -26,6 +31,7 @@ fn part2(input: &str) -> u64 {
     }
     let mut total = 0;
     let mut numbers = Vec::new();
+    #[allow(clippy::needless_range_loop)]
     for col in (0..cols).rev() {
         let mut number = 0u64;
         for row in 0..rows - 1 {
@@ -48,6 +54,27 @@ fn part2(input: &str) -> u64 {
     total
 }
 
+pub struct Day6;
+
+impl Solution for Day6 {
+    const YEAR: u32 = 2025;
+    const DAY: u32 = 6;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        Some(Ok(part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,177 @@
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use aoc_common::solution::Registry;
+use aoc_common::trace;
+
+const USAGE: &str =
+    "usage: aoc run <year> <day> <part> [--input <path>|-] [--trace] [<parameter>...]";
+
+fn registry() -> Registry {
+    let mut registry = Registry::new();
+    aoc_2022::register(&mut registry);
+    aoc_2023::register(&mut registry);
+    aoc_2024::register(&mut registry);
+    aoc_2025::register(&mut registry);
+    registry
+}
+
+/// Extra positional arguments are for the solutions that take more than the input (e.g. the row to
+/// scan in 2022 day 15); when omitted, the values used for the actual puzzle input are used
+fn solve(year: u32, day: u32, part: u32, input: &str, params: &[String]) -> Result<String, String> {
+    let registry = registry();
+    let entry = registry
+        .get(year, day)
+        .ok_or_else(|| format!("no solution for {year} day {day}"))?;
+    entry.run(input, part, params)
+}
+
+fn read_input(path: Option<&str>, year: u32, day: u32) -> Result<String, String> {
+    match path {
+        Some("-") => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("could not read stdin: {e}"))?;
+            Ok(input)
+        }
+        Some(path) => {
+            std::fs::read_to_string(path).map_err(|e| format!("could not read {path}: {e}"))
+        }
+        None => {
+            let path: PathBuf = [
+                env!("CARGO_MANIFEST_DIR"),
+                "..",
+                &year.to_string(),
+                "inputs",
+                &format!("day{day}.txt"),
+            ]
+            .iter()
+            .collect();
+            std::fs::read_to_string(&path)
+                .map_err(|e| format!("could not read {}: {e}", path.display()))
+        }
+    }
+}
+
+fn parse_number(name: &str, value: Option<String>) -> Result<u32, String> {
+    let value = value.ok_or_else(|| format!("missing {name}\n{USAGE}"))?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid {name} {value:?}\n{USAGE}"))
+}
+
+fn run(args: Vec<String>) -> Result<String, String> {
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        Some("run") => (),
+        Some(command) => return Err(format!("unknown command {command:?}\n{USAGE}")),
+        None => return Err(USAGE.to_string()),
+    }
+    let year = parse_number("year", args.next())?;
+    let day = parse_number("day", args.next())?;
+    let part = parse_number("part", args.next())?;
+
+    let mut input_path = None;
+    let mut tracing = false;
+    let mut params = Vec::new();
+    while let Some(arg) = args.next() {
+        if arg == "--input" {
+            let path = args
+                .next()
+                .ok_or_else(|| format!("missing path after --input\n{USAGE}"))?;
+            input_path = Some(path);
+        } else if arg == "--trace" {
+            tracing = true;
+        } else {
+            params.push(arg);
+        }
+    }
+
+    let input = read_input(input_path.as_deref(), year, day)?;
+    if tracing {
+        trace::with_sink(
+            |message| eprintln!("{message}"),
+            || solve(year, day, part, &input, &params),
+        )
+    } else {
+        solve(year, day, part, &input, &params)
+    }
+}
+
+fn main() -> ExitCode {
+    match run(std::env::args().skip(1).collect()) {
+        Ok(answer) => {
+            println!("{answer}");
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_solve() {
+        let example = include_str!("../../2024/examples/day17-1.txt");
+        let answer = solve(2024, 17, 1, example, &params(&[]));
+        assert_eq!(answer.unwrap(), "4,6,3,5,6,3,5,2,1,0");
+        assert!(solve(2024, 26, 1, example, &params(&[])).is_err());
+    }
+
+    #[test]
+    fn test_solve_error() {
+        // the example program does not have the shape expected by part 2
+        let example = include_str!("../../2024/examples/day17-1.txt");
+        let error = solve(2024, 17, 2, example, &params(&[])).unwrap_err();
+        assert!(error.starts_with("2024 day 17: "), "{error}");
+    }
+
+    #[test]
+    fn test_parameters() {
+        let example = include_str!("../../2022/examples/day15.txt");
+        let answer = solve(2022, 15, 1, example, &params(&["10"]));
+        assert_eq!(answer.unwrap(), "26");
+        assert!(solve(2022, 15, 1, example, &params(&["ten"])).is_err());
+        assert!(solve(2022, 15, 1, example, &params(&["10", "20"])).is_err());
+
+        let example = include_str!("../../2023/examples/day13.txt");
+        assert_eq!(solve(2023, 13, 1, example, &params(&[])).unwrap(), "405");
+        assert_eq!(solve(2023, 13, 1, example, &params(&["1"])).unwrap(), "400");
+        let example = include_str!("../../2023/examples/day17-1.txt");
+        assert_eq!(solve(2023, 17, 2, example, &params(&[])).unwrap(), "94");
+        assert_eq!(
+            solve(2023, 17, 2, example, &params(&["1", "3"])).unwrap(),
+            "102"
+        );
+    }
+
+    #[test]
+    fn test_trace() {
+        let example = include_str!("../../2024/examples/day21.txt");
+        let (answer, messages) = trace::capture(|| solve(2024, 21, 1, example, &params(&[])));
+        assert_eq!(answer.unwrap(), "126384");
+        assert!(messages.contains(&"029A: 68 * 29 = 1972".to_string()));
+    }
+
+    #[test]
+    fn test_registry() {
+        let registry = registry();
+        let years: Vec<u32> = registry.iter().map(|entry| entry.year).collect();
+        assert!(years.is_sorted());
+        assert_eq!(
+            registry.iter().filter(|entry| entry.year == 2023).count(),
+            25
+        );
+    }
+}
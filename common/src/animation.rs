@@ -0,0 +1,350 @@
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::grid::{Grid, ToChar};
+
+/// Frames of a grid simulation, each drawn with one character per cell
+///
+/// Images are rendered with one color per character, see `with_color`.
+#[derive(Clone, Debug)]
+pub struct Animation {
+    frames: Vec<(String, Grid<u8>)>,
+    palette: Vec<[u8; 3]>,
+    scale: usize,
+    delay: u16,
+}
+
+fn default_color(c: u8) -> [u8; 3] {
+    match c {
+        b'.' | b' ' => [0, 0, 0],
+        b'#' => [255, 255, 255],
+        // spread the other characters around
+        _ => [
+            c.wrapping_mul(97) | 0x40,
+            c.wrapping_mul(53) | 0x40,
+            c.wrapping_mul(29) | 0x40,
+        ],
+    }
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Animation {
+            frames: Vec::new(),
+            palette: (0..=255).map(default_color).collect(),
+            scale: 4,
+            delay: 10,
+        }
+    }
+}
+
+impl Animation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Draw the cells shown as `c` with the given RGB color in images
+    pub fn with_color(mut self, c: char, rgb: [u8; 3]) -> Self {
+        self.palette[c as usize & 0xff] = rgb;
+        self
+    }
+
+    /// Size in pixels of a cell in images
+    pub fn with_scale(mut self, scale: usize) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Time each frame stays on screen in the GIF, in hundredths of a second
+    pub fn with_delay(mut self, delay: u16) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Record a frame, with a caption shown in the text log
+    pub fn push<T: ToChar>(&mut self, caption: impl Display, frame: &Grid<T>) {
+        let frame = frame.map(|cell| {
+            let c = cell.to_char();
+            if c.is_ascii() {
+                c as u8
+            } else {
+                b'?'
+            }
+        });
+        self.frames.push((caption.to_string(), frame));
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn frame(&self, index: usize) -> &Grid<u8> {
+        &self.frames[index].1
+    }
+
+    /// All the frames one after the other, each after its caption
+    pub fn text_log(&self) -> String {
+        let mut log = String::new();
+        for (caption, frame) in &self.frames {
+            log.push_str(&format!("{caption}\n{frame}\n"));
+        }
+        log
+    }
+
+    /// Character of every pixel of a frame, scaled and padded to `width` by `height` pixels
+    fn pixels(&self, frame: &Grid<u8>, width: usize, height: usize) -> Vec<u8> {
+        let mut indices = vec![b' '; width * height];
+        for ((i, j), &c) in frame.iter() {
+            for y in i * self.scale..(i + 1) * self.scale {
+                let row = &mut indices[y * width..(y + 1) * width];
+                row[j * self.scale..(j + 1) * self.scale].fill(c);
+            }
+        }
+        indices
+    }
+
+    fn size(&self, frame: &Grid<u8>) -> (usize, usize) {
+        (frame.cols() * self.scale, frame.rows() * self.scale)
+    }
+
+    /// Frame as a binary PPM image
+    pub fn ppm(&self, index: usize) -> Vec<u8> {
+        let frame = self.frame(index);
+        let (width, height) = self.size(frame);
+        let mut image = format!("P6\n{width} {height}\n255\n").into_bytes();
+        for c in self.pixels(frame, width, height) {
+            image.extend(self.palette[c as usize]);
+        }
+        image
+    }
+
+    /// Frame as a PNG image
+    pub fn png(&self, index: usize) -> Vec<u8> {
+        let frame = self.frame(index);
+        let (width, height) = self.size(frame);
+        let pixels = self.pixels(frame, width, height);
+        let mut scanlines = Vec::with_capacity(height * (3 * width + 1));
+        for row in pixels.chunks(width.max(1)) {
+            // no filter
+            scanlines.push(0);
+            for &c in row {
+                scanlines.extend(self.palette[c as usize]);
+            }
+        }
+
+        let mut header = Vec::new();
+        header.extend((width as u32).to_be_bytes());
+        header.extend((height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, default compression, filtering and no interlacing
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut image = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut image, b"IHDR", &header);
+        png_chunk(&mut image, b"IDAT", &zlib_stored(&scanlines));
+        png_chunk(&mut image, b"IEND", &[]);
+        image
+    }
+
+    /// All the frames as a looping animated GIF
+    pub fn gif(&self) -> Vec<u8> {
+        let width = self.frames.iter().map(|(_, f)| self.size(f).0).max();
+        let height = self.frames.iter().map(|(_, f)| self.size(f).1).max();
+        let (width, height) = (width.unwrap_or(0), height.unwrap_or(0));
+
+        let mut image = b"GIF89a".to_vec();
+        image.extend((width as u16).to_le_bytes());
+        image.extend((height as u16).to_le_bytes());
+        // global color table of 256 colors, one per character
+        image.extend([0xf7, 0, 0]);
+        for color in &self.palette {
+            image.extend(color);
+        }
+        // loop forever
+        image.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+        for (_, frame) in &self.frames {
+            image.extend([0x21, 0xf9, 4, 0]);
+            image.extend(self.delay.to_le_bytes());
+            image.extend([0, 0]);
+            image.push(0x2c);
+            image.extend([0, 0, 0, 0]);
+            image.extend((width as u16).to_le_bytes());
+            image.extend((height as u16).to_le_bytes());
+            image.push(0);
+            image.push(8);
+            let data = lzw_uncompressed(&self.pixels(frame, width, height));
+            for block in data.chunks(255) {
+                image.push(block.len() as u8);
+                image.extend(block);
+            }
+            image.push(0);
+        }
+        image.push(0x3b);
+        image
+    }
+
+    /// Write `text_log` to a file
+    pub fn write_text_log(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.text_log())
+    }
+
+    /// Write one PPM image per frame to `directory`, as `frame0000.ppm`, `frame0001.ppm`, etc.
+    pub fn write_ppm_sequence(&self, directory: impl AsRef<Path>) -> io::Result<()> {
+        self.write_sequence(directory.as_ref(), "ppm", Self::ppm)
+    }
+
+    /// Write one PNG image per frame to `directory`, as `frame0000.png`, `frame0001.png`, etc.
+    pub fn write_png_sequence(&self, directory: impl AsRef<Path>) -> io::Result<()> {
+        self.write_sequence(directory.as_ref(), "png", Self::png)
+    }
+
+    fn write_sequence(
+        &self,
+        directory: &Path,
+        extension: &str,
+        encode: fn(&Self, usize) -> Vec<u8>,
+    ) -> io::Result<()> {
+        fs::create_dir_all(directory)?;
+        for index in 0..self.len() {
+            let path = directory.join(format!("frame{index:04}.{extension}"));
+            fs::write(path, encode(self, index))?;
+        }
+        Ok(())
+    }
+
+    /// Write `gif` to a file
+    pub fn write_gif(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.gif())
+    }
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb88320 & mask);
+        }
+    }
+    !crc
+}
+
+fn png_chunk(image: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    image.extend((data.len() as u32).to_be_bytes());
+    let start = image.len();
+    image.extend(kind);
+    image.extend(data);
+    let crc = crc32(&image[start..]);
+    image.extend(crc.to_be_bytes());
+}
+
+/// zlib stream made of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        stream.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        stream.push(last as u8);
+        let len = block.len() as u16;
+        stream.extend(len.to_le_bytes());
+        stream.extend((!len).to_le_bytes());
+        stream.extend(block);
+    }
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    stream.extend(((b << 16) | a).to_be_bytes());
+    stream
+}
+
+/// GIF image data with 8-bit codes, emitted as literals
+///
+/// The dictionary is cleared often enough that codes stay 9 bits long, which avoids implementing
+/// actual compression.
+fn lzw_uncompressed(indices: &[u8]) -> Vec<u8> {
+    const CLEAR: u32 = 256;
+    const END: u32 = 257;
+    let mut data = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    let mut emit = |code: u32| {
+        buffer |= code << bits;
+        bits += 9;
+        while bits >= 8 {
+            data.push(buffer as u8);
+            buffer >>= 8;
+            bits -= 8;
+        }
+    };
+    for chunk in indices.chunks(128) {
+        emit(CLEAR);
+        for &index in chunk {
+            emit(index as u32);
+        }
+    }
+    emit(END);
+    if bits > 0 {
+        data.push(buffer as u8);
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn animation() -> Animation {
+        let mut animation = Animation::new().with_scale(2).with_color('o', [255, 0, 0]);
+        animation.push("start", &Grid::parse("#.\n.o\n").unwrap());
+        animation.push("end", &Grid::new(1, 3, true));
+        animation
+    }
+
+    #[test]
+    fn test_text_log() {
+        assert_eq!(animation().text_log(), "start\n#.\n.o\n\nend\n###\n\n");
+    }
+
+    #[test]
+    fn test_images() {
+        let animation = animation();
+
+        let ppm = animation.ppm(0);
+        let header = b"P6\n4 4\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 4 * 4 * 3);
+        // bottom right pixel
+        assert_eq!(ppm[ppm.len() - 3..], [255, 0, 0]);
+
+        let png = animation.png(1);
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..24], b"IHDR\x00\x00\x00\x06\x00\x00\x00\x02");
+        assert_eq!(
+            &png[png.len() - 12..],
+            b"\x00\x00\x00\x00IEND\xae\x42\x60\x82"
+        );
+
+        let gif = animation.gif();
+        assert_eq!(&gif[..10], b"GIF89a\x06\x00\x04\x00");
+        assert_eq!(gif.last(), Some(&0x3b));
+    }
+
+    #[test]
+    fn test_zlib_stored() {
+        // the adler32 checksum of "abc"
+        let stream = zlib_stored(b"abc");
+        assert_eq!(stream, b"\x78\x01\x01\x03\x00\xfc\xffabc\x02\x4d\x01\x27");
+    }
+}
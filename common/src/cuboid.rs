@@ -0,0 +1,191 @@
+/// Axis-aligned box of integer points, the product of half-open ranges
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Box<const N: usize> {
+    pub start: [i64; N],
+    pub end: [i64; N],
+}
+
+impl<const N: usize> Box<N> {
+    pub fn new(start: [i64; N], end: [i64; N]) -> Self {
+        Box { start, end }
+    }
+
+    /// Box from its smallest and largest corners, both included
+    pub fn from_inclusive(min: [i64; N], max: [i64; N]) -> Self {
+        Box {
+            start: min,
+            end: max.map(|v| v + 1),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (0..N).any(|axis| self.start[axis] >= self.end[axis])
+    }
+
+    /// Number of points, along `axis`
+    pub fn size(&self, axis: usize) -> i64 {
+        (self.end[axis] - self.start[axis]).max(0)
+    }
+
+    /// Number of points
+    pub fn volume(&self) -> i64 {
+        (0..N).map(|axis| self.size(axis)).product()
+    }
+
+    pub fn contains(&self, point: [i64; N]) -> bool {
+        (0..N).all(|axis| self.start[axis] <= point[axis] && point[axis] < self.end[axis])
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let start = std::array::from_fn(|axis| self.start[axis].max(other.start[axis]));
+        let end = std::array::from_fn(|axis| self.end[axis].min(other.end[axis]));
+        let intersection = Box { start, end };
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Points with a coordinate along `axis` below `at`, and the others
+    pub fn split(&self, axis: usize, at: i64) -> (Option<Self>, Option<Self>) {
+        let mut below = *self;
+        below.end[axis] = below.end[axis].min(at);
+        let mut above = *self;
+        above.start[axis] = above.start[axis].max(at);
+        let non_empty = |b: Box<N>| (!b.is_empty()).then_some(b);
+        (non_empty(below), non_empty(above))
+    }
+
+    /// Points not in `other`, as at most `2 * N` disjoint boxes
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        let Some(hole) = self.intersection(other) else {
+            return vec![*self];
+        };
+        // peel off the slabs on both sides of the hole, one axis after the other
+        let mut pieces = Vec::new();
+        let mut rest = *self;
+        for axis in 0..N {
+            let (below, middle) = rest.split(axis, hole.start[axis]);
+            let (middle, above) = middle.unwrap().split(axis, hole.end[axis]);
+            pieces.extend(below);
+            pieces.extend(above);
+            rest = middle.unwrap();
+        }
+        pieces
+    }
+
+    /// Same box, moved by `offset`
+    pub fn translate(&self, offset: [i64; N]) -> Self {
+        Box {
+            start: std::array::from_fn(|axis| self.start[axis] + offset[axis]),
+            end: std::array::from_fn(|axis| self.end[axis] + offset[axis]),
+        }
+    }
+}
+
+/// Union of boxes, stored as disjoint boxes
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BoxSet<const N: usize> {
+    boxes: Vec<Box<N>>,
+}
+
+impl<const N: usize> BoxSet<N> {
+    pub fn new() -> Self {
+        BoxSet { boxes: Vec::new() }
+    }
+
+    /// Disjoint boxes covering the set
+    pub fn boxes(&self) -> &[Box<N>] {
+        &self.boxes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boxes.is_empty()
+    }
+
+    /// Number of points
+    pub fn volume(&self) -> i64 {
+        self.boxes.iter().map(Box::volume).sum()
+    }
+
+    pub fn contains(&self, point: [i64; N]) -> bool {
+        self.boxes.iter().any(|b| b.contains(point))
+    }
+
+    pub fn insert(&mut self, new: Box<N>) {
+        // only add the points which are not already there
+        let mut pieces = vec![new];
+        for b in &self.boxes {
+            pieces = pieces.iter().flat_map(|piece| piece.subtract(b)).collect();
+        }
+        self.boxes
+            .extend(pieces.into_iter().filter(|piece| !piece.is_empty()));
+    }
+
+    pub fn remove(&mut self, hole: &Box<N>) {
+        self.boxes = self.boxes.iter().flat_map(|b| b.subtract(hole)).collect();
+    }
+
+    pub fn intersection(&self, other: &Box<N>) -> Self {
+        let boxes = self
+            .boxes
+            .iter()
+            .filter_map(|b| b.intersection(other))
+            .collect();
+        BoxSet { boxes }
+    }
+}
+
+impl<const N: usize> FromIterator<Box<N>> for BoxSet<N> {
+    fn from_iter<I: IntoIterator<Item = Box<N>>>(iter: I) -> Self {
+        let mut set = BoxSet::new();
+        for b in iter {
+            set.insert(b);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_box() {
+        let a = Box::from_inclusive([0, 0, 0], [9, 9, 9]);
+        assert_eq!(a.volume(), 1000);
+        assert!(a.contains([9, 0, 5]) && !a.contains([10, 0, 5]));
+        let b = Box::new([5, 5, 5], [15, 15, 15]);
+        assert_eq!(a.intersection(&b), Some(Box::new([5; 3], [10; 3])));
+        assert!(!a.intersects(&b.translate([5, 0, 0])));
+
+        let pieces = a.subtract(&b);
+        assert_eq!(pieces.iter().map(Box::volume).sum::<i64>(), 1000 - 125);
+        for (i, p) in pieces.iter().enumerate() {
+            assert!(!p.intersects(&b));
+            assert!(pieces[i + 1..].iter().all(|q| !p.intersects(q)));
+        }
+        // a hole in the middle leaves a slab on each side
+        let hole = Box::new([1, 1, 1], [9, 9, 9]);
+        assert_eq!(a.subtract(&hole).len(), 6);
+        assert_eq!(a.subtract(&a), []);
+
+        assert_eq!(a.split(2, 3).0.map(|b| b.volume()), Some(300));
+        assert_eq!(a.split(2, 0).0, None);
+    }
+
+    #[test]
+    fn test_box_set() {
+        // the reboot steps of the 2021 day 22 example
+        let mut set = BoxSet::new();
+        set.insert(Box::from_inclusive([10, 10, 10], [12, 12, 12]));
+        set.insert(Box::from_inclusive([11, 11, 11], [13, 13, 13]));
+        set.remove(&Box::from_inclusive([9, 9, 9], [11, 11, 11]));
+        set.insert(Box::from_inclusive([10, 10, 10], [10, 10, 10]));
+        assert_eq!(set.volume(), 39);
+        assert!(set.contains([10, 10, 10]) && !set.contains([11, 11, 11]));
+        let corner = set.intersection(&Box::new([13, 13, 13], [20, 20, 20]));
+        assert_eq!(corner.volume(), 1);
+    }
+}
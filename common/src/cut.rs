@@ -0,0 +1,162 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+
+/// Partition of the nodes of a graph in two
+#[derive(Clone, Debug)]
+pub struct Cut<N> {
+    /// Edges between the two sides, in the order they were given
+    pub edges: Vec<(N, N)>,
+    pub sides: [Vec<N>; 2],
+}
+
+impl<N> Cut<N> {
+    pub fn sizes(&self) -> [usize; 2] {
+        [self.sides[0].len(), self.sides[1].len()]
+    }
+}
+
+/// Cut of an undirected graph with the fewest edges between the two sides, using the
+/// Stoer–Wagner algorithm; `None` when there are less than two nodes
+///
+/// Parallel edges count separately; self-loops are ignored.
+pub fn min_cut<N>(edges: &[(N, N)]) -> Option<Cut<N>>
+where
+    N: Clone + Eq + Hash,
+{
+    // number the nodes
+    let mut indices = HashMap::new();
+    let mut nodes = Vec::new();
+    let mut index = |node: &N| {
+        *indices.entry(node.clone()).or_insert_with(|| {
+            nodes.push(node.clone());
+            nodes.len() - 1
+        })
+    };
+    let edge_indices: Vec<(usize, usize)> =
+        edges.iter().map(|(a, b)| (index(a), index(b))).collect();
+    let n = nodes.len();
+    if n < 2 {
+        return None;
+    }
+
+    // weights between the groups of merged nodes
+    let mut adjacency: Vec<HashMap<usize, usize>> = vec![HashMap::new(); n];
+    for &(a, b) in &edge_indices {
+        if a != b {
+            *adjacency[a].entry(b).or_default() += 1;
+            *adjacency[b].entry(a).or_default() += 1;
+        }
+    }
+    let mut members: Vec<Vec<usize>> = (0..n).map(|i| vec![i]).collect();
+    let mut active: Vec<usize> = (0..n).collect();
+
+    let mut best: Option<(usize, Vec<usize>)> = None;
+    while active.len() > 1 {
+        // add the groups one by one, always the most tightly connected to the previous ones
+        let mut connection = vec![0; n];
+        let mut added = vec![false; n];
+        let mut q = BinaryHeap::new();
+        let mut order = Vec::with_capacity(active.len());
+        let mut cut_of_the_phase = 0;
+        let mut unreached = active.iter();
+        while order.len() < active.len() {
+            let (weight, group) = match q.pop() {
+                Some((weight, group)) => (weight, group),
+                // start, or continue in another connected component
+                None => (0, *unreached.find(|&&group| !added[group]).unwrap()),
+            };
+            if added[group] || weight != connection[group] {
+                continue;
+            }
+            added[group] = true;
+            order.push(group);
+            cut_of_the_phase = weight;
+            for (&neighbor, &w) in &adjacency[group] {
+                if !added[neighbor] {
+                    connection[neighbor] += w;
+                    q.push((connection[neighbor], neighbor));
+                }
+            }
+        }
+
+        // the last group is separated from the others by the cut of the phase
+        let &[.., s, t] = order.as_slice() else {
+            unreachable!()
+        };
+        if best
+            .as_ref()
+            .is_none_or(|&(weight, _)| cut_of_the_phase < weight)
+        {
+            best = Some((cut_of_the_phase, members[t].clone()));
+        }
+
+        // merge t into s
+        let t_members = std::mem::take(&mut members[t]);
+        members[s].extend(t_members);
+        for (neighbor, w) in std::mem::take(&mut adjacency[t]) {
+            adjacency[neighbor].remove(&t);
+            if neighbor != s {
+                *adjacency[s].entry(neighbor).or_default() += w;
+                *adjacency[neighbor].entry(s).or_default() += w;
+            }
+        }
+        active.retain(|&group| group != t);
+    }
+
+    let (_, side) = best.unwrap();
+    let side: HashSet<usize> = side.into_iter().collect();
+    let cut_edges = edges
+        .iter()
+        .zip(&edge_indices)
+        .filter(|(_, (a, b))| side.contains(a) != side.contains(b))
+        .map(|(edge, _)| edge.clone())
+        .collect();
+    let mut sides = [Vec::new(), Vec::new()];
+    for (i, node) in nodes.into_iter().enumerate() {
+        sides[usize::from(!side.contains(&i))].push(node);
+    }
+    Some(Cut {
+        edges: cut_edges,
+        sides,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_cut() {
+        // two complete graphs of 4 nodes, joined by two edges
+        let edges = [
+            (1, 2),
+            (1, 3),
+            (1, 4),
+            (2, 3),
+            (2, 4),
+            (3, 4),
+            (5, 6),
+            (5, 7),
+            (5, 8),
+            (6, 7),
+            (6, 8),
+            (7, 8),
+            (2, 5),
+            (3, 8),
+        ];
+        let cut = min_cut(&edges).unwrap();
+        assert_eq!(cut.edges, [(2, 5), (3, 8)]);
+        let mut sides = cut.sides.clone();
+        sides.iter_mut().for_each(|side| side.sort());
+        sides.sort();
+        assert_eq!(sides, [vec![1, 2, 3, 4], vec![5, 6, 7, 8]]);
+        assert_eq!(cut.sizes(), [4, 4]);
+
+        // disconnected
+        let cut = min_cut(&[("a", "b"), ("c", "d"), ("d", "e")]).unwrap();
+        assert!(cut.edges.is_empty());
+        assert_eq!(cut.sizes()[0] + cut.sizes()[1], 5);
+
+        assert!(min_cut(&[(1, 1)]).is_none());
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Sequence of states that eventually repeats itself
+#[derive(Clone, Debug)]
+pub struct Cycle<T> {
+    /// Number of steps before entering the cycle
+    pub start: usize,
+    /// Number of steps to go around the cycle
+    pub period: usize,
+    /// The states before the cycle, followed by one round of the cycle, and the first state of
+    /// the cycle again (only its key is the same as the first time)
+    pub states: Vec<T>,
+}
+
+impl<T> Cycle<T> {
+    /// State after `n` steps
+    pub fn nth(&self, n: usize) -> &T {
+        &self.states[self.index(n)]
+    }
+
+    fn index(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+
+    /// Value after `n` steps of a quantity which increases by the same amount at each round of the
+    /// cycle, such as the height of a tower whose top keeps repeating
+    pub fn extrapolate(&self, n: usize, value: impl Fn(&T) -> i64) -> i64 {
+        let base = value(self.nth(n));
+        if n < self.start {
+            return base;
+        }
+        let increase =
+            value(&self.states[self.start + self.period]) - value(&self.states[self.start]);
+        let rounds = ((n - self.start) / self.period) as i64;
+        base + rounds * increase
+    }
+}
+
+/// Apply `step` from `initial` until two states have the same `key`
+///
+/// Only the key is used to compare states, so it should capture everything that determines the
+/// following states.
+pub fn find_cycle<T, K>(
+    initial: T,
+    mut step: impl FnMut(&T) -> T,
+    mut key: impl FnMut(&T) -> K,
+) -> Cycle<T>
+where
+    K: Eq + Hash,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![initial];
+    loop {
+        let current = states.last().unwrap();
+        let index = states.len() - 1;
+        if let Some(start) = seen.insert(key(current), index) {
+            return Cycle {
+                start,
+                period: index - start,
+                states,
+            };
+        }
+        let next = step(current);
+        states.push(next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_cycle() {
+        // 0, 1, 2, 3, 4, 5, 3, 4, 5, ...
+        let cycle = find_cycle(0, |&n| if n == 5 { 3 } else { n + 1 }, |&n| n);
+        assert_eq!((cycle.start, cycle.period), (3, 3));
+        assert_eq!(*cycle.nth(2), 2);
+        assert_eq!(*cycle.nth(7), 4);
+        assert_eq!(*cycle.nth(1_000_000_000), 4);
+
+        // a counter that keeps going up, but whose last digit cycles
+        let cycle = find_cycle(7i64, |&n| n + 3, |&n| n % 10);
+        assert_eq!((cycle.start, cycle.period), (0, 10));
+        assert_eq!(cycle.extrapolate(4, |&n| n), 19);
+        assert_eq!(cycle.extrapolate(1_000_000_000, |&n| n), 3_000_000_007);
+    }
+}
@@ -0,0 +1,95 @@
+use std::fmt::{self, Display, Formatter};
+
+/// Graph in the DOT language of Graphviz, e.g. to render with `dot -Tsvg`
+#[derive(Clone, Debug)]
+pub struct Dot {
+    directed: bool,
+    statements: Vec<String>,
+}
+
+fn quote(s: &str) -> String {
+    let s = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{s}\"")
+}
+
+fn attribute_list(attributes: &[(&str, &str)]) -> String {
+    if attributes.is_empty() {
+        return String::new();
+    }
+    let attributes: Vec<String> = attributes
+        .iter()
+        .map(|(name, value)| format!("{name}={}", quote(value)))
+        .collect();
+    format!(" [{}]", attributes.join(", "))
+}
+
+impl Dot {
+    pub fn digraph() -> Self {
+        Dot {
+            directed: true,
+            statements: Vec::new(),
+        }
+    }
+
+    pub fn graph() -> Self {
+        Dot {
+            directed: false,
+            statements: Vec::new(),
+        }
+    }
+
+    /// Declare a node; only needed to give it attributes such as `shape` or `label`
+    pub fn node(&mut self, id: &str, attributes: &[(&str, &str)]) -> &mut Self {
+        let statement = format!("{}{}", quote(id), attribute_list(attributes));
+        self.statements.push(statement);
+        self
+    }
+
+    pub fn edge(&mut self, from: &str, to: &str, attributes: &[(&str, &str)]) -> &mut Self {
+        let arrow = if self.directed { "->" } else { "--" };
+        let statement = format!(
+            "{} {arrow} {}{}",
+            quote(from),
+            quote(to),
+            attribute_list(attributes)
+        );
+        self.statements.push(statement);
+        self
+    }
+}
+
+impl Display for Dot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let kind = if self.directed { "digraph" } else { "graph" };
+        writeln!(f, "{kind} {{")?;
+        for statement in &self.statements {
+            writeln!(f, "    {statement};")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dot() {
+        let mut dot = Dot::digraph();
+        dot.node("a", &[("shape", "box")]).edge("a", "b", &[]).edge(
+            "b",
+            "say \"hi\"",
+            &[("label", "x")],
+        );
+        assert_eq!(
+            dot.to_string(),
+            "digraph {\n    \"a\" [shape=\"box\"];\n    \"a\" -> \"b\";\n    \"b\" -> \"say \\\"hi\\\"\" [label=\"x\"];\n}\n"
+        );
+        let mut dot = Dot::graph();
+        dot.edge("a", "b", &[]);
+        assert_eq!(dot.to_string(), "graph {\n    \"a\" -- \"b\";\n}\n");
+    }
+}
@@ -0,0 +1,125 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// Malformed puzzle input
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// Puzzle whose input is being parsed, as `(year, day)`, once known
+    pub puzzle: Option<(u32, u32)>,
+    /// Line number, starting from 1
+    pub line: usize,
+    /// Offending text
+    pub text: String,
+    /// Description of what should have been there instead
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, text: &str, expected: impl Into<String>) -> Self {
+        ParseError {
+            puzzle: None,
+            line,
+            text: text.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    pub fn in_puzzle(self, year: u32, day: u32) -> Self {
+        ParseError {
+            puzzle: Some((year, day)),
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some((year, day)) = self.puzzle {
+            write!(f, "{year} day {day}, ")?;
+        }
+        write!(
+            f,
+            "line {}: expected {}, found {:?}",
+            self.line, self.expected, self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Well-formed puzzle input which a solution cannot handle, e.g. because it lacks a property that
+/// the solution relies on
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SolveError {
+    /// Puzzle being solved, as `(year, day)`, once known
+    pub puzzle: Option<(u32, u32)>,
+    pub message: String,
+}
+
+impl SolveError {
+    pub fn new(message: impl Into<String>) -> Self {
+        SolveError {
+            puzzle: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn in_puzzle(self, year: u32, day: u32) -> Self {
+        SolveError {
+            puzzle: Some((year, day)),
+            ..self
+        }
+    }
+}
+
+impl From<String> for SolveError {
+    fn from(message: String) -> Self {
+        SolveError::new(message)
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some((year, day)) = self.puzzle {
+            write!(f, "{year} day {day}: ")?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Parse `text`, found on the given line, or report it as not being `expected`
+pub fn parse<T: FromStr>(line: usize, text: &str, expected: &str) -> Result<T, ParseError> {
+    text.parse()
+        .map_err(|_| ParseError::new(line, text, expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse::<u32>(3, "42", "a number"), Ok(42));
+        let error = parse::<u32>(3, "4x2", "a number").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3: expected a number, found \"4x2\""
+        );
+        assert_eq!(
+            error.in_puzzle(2022, 16).to_string(),
+            "2022 day 16, line 3: expected a number, found \"4x2\""
+        );
+    }
+
+    #[test]
+    fn test_solve_error() {
+        let error = SolveError::from("no loop".to_string());
+        assert_eq!(error.to_string(), "no loop");
+        assert_eq!(
+            error.in_puzzle(2024, 17).to_string(),
+            "2024 day 17: no loop"
+        );
+    }
+}
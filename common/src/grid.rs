@@ -0,0 +1,364 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+use crate::error::ParseError;
+
+/// Up, left, right, down, as `(di, dj)`
+pub const DIRECTIONS4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Same as `DIRECTIONS4`, with the diagonals
+pub const DIRECTIONS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Rectangular grid of cells, indexed by `(row, column)`
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            rows,
+            cols,
+            cells: vec![value; rows * cols],
+        }
+    }
+
+    /// Panics if the rows do not all have the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let cols = rows.first().map_or(0, |row| row.len());
+        let n_rows = rows.len();
+        let mut cells = Vec::with_capacity(n_rows * cols);
+        for row in rows {
+            assert_eq!(row.len(), cols, "all rows should have the same length");
+            cells.extend(row);
+        }
+        Grid {
+            rows: n_rows,
+            cols,
+            cells,
+        }
+    }
+
+    /// Parse one cell per byte with `f`, which returns `None` for unexpected bytes
+    pub fn parse_with<F>(input: &str, expected: &str, mut f: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(u8) -> Option<T>,
+    {
+        let mut rows = 0;
+        let mut cols = 0;
+        let mut cells = Vec::with_capacity(input.len());
+        for (i, line) in input.lines().enumerate() {
+            if i == 0 {
+                cols = line.len();
+            } else if line.len() != cols {
+                let expected = format!("a row of {cols} cells");
+                return Err(ParseError::new(i + 1, line, expected));
+            }
+            for (j, &c) in line.as_bytes().iter().enumerate() {
+                let cell = f(c).ok_or_else(|| {
+                    let text = line.get(j..j + 1).unwrap_or(line);
+                    ParseError::new(i + 1, text, expected)
+                })?;
+                cells.push(cell);
+            }
+            rows += 1;
+        }
+        Ok(Grid { rows, cols, cells })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Return the position `(di, dj)` away from `(i, j)`, if it is inside the grid
+    pub fn offset(
+        &self,
+        (i, j): (usize, usize),
+        (di, dj): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let i = i.checked_add_signed(di)?;
+        let j = j.checked_add_signed(dj)?;
+        (i < self.rows && j < self.cols).then_some((i, j))
+    }
+
+    pub fn contains(&self, i: isize, j: isize) -> bool {
+        (0..self.rows as isize).contains(&i) && (0..self.cols as isize).contains(&j)
+    }
+
+    pub fn get(&self, i: isize, j: isize) -> Option<&T> {
+        self.contains(i, j)
+            .then(|| &self.cells[i as usize * self.cols + j as usize])
+    }
+
+    pub fn get_mut(&mut self, i: isize, j: isize) -> Option<&mut T> {
+        if self.contains(i, j) {
+            Some(&mut self.cells[i as usize * self.cols + j as usize])
+        } else {
+            None
+        }
+    }
+
+    /// All the positions, row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |i| (0..cols).map(move |j| (i, j)))
+    }
+
+    /// All the cells with their positions, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Positions directly above, left, right and below `(i, j)`
+    pub fn neighbors4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        DIRECTIONS4
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d))
+    }
+
+    /// Same as `neighbors4`, with the diagonals
+    pub fn neighbors8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        DIRECTIONS8
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d))
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.cells[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        &mut self.cells[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn column(&self, j: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(j < self.cols);
+        self.cells[j..].iter().step_by(self.cols)
+    }
+
+    /// Cells from `(i, j)` included, in the direction `(di, dj)`, until leaving the grid
+    pub fn ray(&self, pos: (usize, usize), direction: (isize, isize)) -> impl Iterator<Item = &T> {
+        let first = (pos.0 < self.rows && pos.1 < self.cols).then_some(pos);
+        std::iter::successors(first, move |&pos| self.offset(pos, direction)).map(|pos| &self[pos])
+    }
+
+    /// Diagonal going down and right from `(i, j)`
+    pub fn diagonal(&self, pos: (usize, usize)) -> impl Iterator<Item = &T> {
+        self.ray(pos, (1, 1))
+    }
+
+    /// Diagonal going down and left from `(i, j)`
+    pub fn anti_diagonal(&self, pos: (usize, usize)) -> impl Iterator<Item = &T> {
+        self.ray(pos, (1, -1))
+    }
+
+    pub fn find(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        let index = self.cells.iter().position(|cell| cell == value)?;
+        Some((index / self.cols, index % self.cols))
+    }
+
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = (usize, usize)> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            rows: self.rows,
+            cols: self.cols,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Swap rows and columns
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.cols)
+            .flat_map(|j| self.column(j).cloned())
+            .collect();
+        Grid {
+            rows: self.cols,
+            cols: self.rows,
+            cells,
+        }
+    }
+
+    /// Rotate by a quarter turn clockwise: the first column, read bottom-up, becomes the first row
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.cols)
+            .flat_map(|j| self.column(j).rev().cloned())
+            .collect();
+        Grid {
+            rows: self.cols,
+            cols: self.rows,
+            cells,
+        }
+    }
+
+    /// Rotate by a quarter turn counter-clockwise
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.cols)
+            .rev()
+            .flat_map(|j| self.column(j).cloned())
+            .collect();
+        Grid {
+            rows: self.cols,
+            cols: self.rows,
+            cells,
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Parse a block of text with one byte per cell
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Grid::parse_with(input, "a byte", Some)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(i < self.rows && j < self.cols, "({i}, {j}) out of grid");
+        &self.cells[i * self.cols + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        assert!(i < self.rows && j < self.cols, "({i}, {j}) out of grid");
+        &mut self.cells[i * self.cols + j]
+    }
+}
+
+/// Cells that can be drawn with a single character
+pub trait ToChar {
+    fn to_char(&self) -> char;
+}
+
+impl ToChar for u8 {
+    fn to_char(&self) -> char {
+        *self as char
+    }
+}
+
+impl ToChar for char {
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+impl ToChar for bool {
+    fn to_char(&self) -> char {
+        if *self {
+            '#'
+        } else {
+            '.'
+        }
+    }
+}
+
+impl<T: ToChar> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for i in 0..self.rows {
+            let row: String = self.row(i).iter().map(ToChar::to_char).collect();
+            writeln!(f, "{row}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "abc\ndef\n";
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse(EXAMPLE).unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid[(1, 2)], b'f');
+        assert_eq!(grid.to_string(), EXAMPLE);
+
+        let error = Grid::parse("abc\nde\n").unwrap_err();
+        assert_eq!(error.line, 2);
+        let error = Grid::parse_with("12\n3x\n", "a digit", |c| c.is_ascii_digit().then_some(c))
+            .unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (2, "x"));
+    }
+
+    #[test]
+    fn test_access() {
+        let grid = Grid::parse(EXAMPLE).unwrap();
+        assert_eq!(grid.get(0, 0), Some(&b'a'));
+        assert_eq!(grid.get(-1, 0), None);
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid.row(1), b"def");
+        assert!(grid.column(1).eq(b"be"));
+        assert!(grid.diagonal((0, 0)).eq(b"ae"));
+        assert!(grid.anti_diagonal((0, 2)).eq(b"ce"));
+        assert!(grid.ray((1, 2), (0, -1)).eq(b"fed"));
+        assert_eq!(grid.find(&b'e'), Some((1, 1)));
+        assert_eq!(grid.find(&b'z'), None);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 3, 0);
+        let mut neighbors: Vec<_> = grid.neighbors4((0, 1)).collect();
+        neighbors.sort();
+        assert_eq!(neighbors, [(0, 0), (0, 2), (1, 1)]);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8((2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn test_transformations() {
+        let grid = Grid::parse(EXAMPLE).unwrap();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad\n");
+        let mut rotated = grid.clone();
+        for _ in 0..4 {
+            rotated = rotated.rotate_clockwise();
+        }
+        assert_eq!(rotated, grid);
+        assert_eq!(grid.map(|&c| c == b'e').to_string(), "...\n.#.\n");
+    }
+}
@@ -0,0 +1,320 @@
+use std::iter::Sum;
+use std::ops::{Add, Range, RangeInclusive, Sub};
+
+/// Set of values stored as sorted, disjoint and non-adjacent half-open ranges
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { ranges: Vec::new() }
+    }
+
+    /// The ranges of the set, in increasing order
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Smallest value of the set
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    /// Number of ranges before `value`, i.e. of the first one that could contain it
+    fn position(&self, value: T) -> usize {
+        self.ranges.partition_point(|range| range.end <= value)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges
+            .get(self.position(value))
+            .is_some_and(|range| range.start <= value)
+    }
+
+    /// Whether all the values of `range` are in the set
+    pub fn contains_range(&self, range: Range<T>) -> bool {
+        if range.is_empty() {
+            return true;
+        }
+        self.ranges
+            .get(self.position(range.start))
+            .is_some_and(|other| other.start <= range.start && range.end <= other.end)
+    }
+
+    /// Whether some values of `range` are in the set
+    pub fn overlaps(&self, range: Range<T>) -> bool {
+        !range.is_empty()
+            && self
+                .ranges
+                .get(self.position(range.start))
+                .is_some_and(|other| other.start < range.end)
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        // ranges which overlap or touch the new one are merged into it
+        let first = self.ranges.partition_point(|other| other.end < range.start);
+        let last = self
+            .ranges
+            .partition_point(|other| other.start <= range.end);
+        let merged = if first < last {
+            let start = range.start.min(self.ranges[first].start);
+            let end = range.end.max(self.ranges[last - 1].end);
+            start..end
+        } else {
+            range
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.position(range.start);
+        let last = self.ranges.partition_point(|other| other.start < range.end);
+        if first >= last {
+            return;
+        }
+        // keep what sticks out on either side
+        let before = self.ranges[first].start..range.start;
+        let after = range.end..self.ranges[last - 1].end;
+        let kept = [before, after].into_iter().filter(|r| !r.is_empty());
+        self.ranges.splice(first..last, kept);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut ranges = self.ranges.clone();
+        ranges.extend(other.ranges.iter().cloned());
+        ranges.into_iter().collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let range = a.start.max(b.start)..a.end.min(b.end);
+            if !range.is_empty() {
+                ranges.push(range);
+            }
+            // the range which ends first cannot overlap anything else
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut j = 0;
+        for range in &self.ranges {
+            let mut start = range.start;
+            // skip the ranges of `other` entirely before this one
+            while j < other.ranges.len() && other.ranges[j].end <= start {
+                j += 1;
+            }
+            let mut k = j;
+            while k < other.ranges.len() && other.ranges[k].start < range.end {
+                if start < other.ranges[k].start {
+                    ranges.push(start..other.ranges[k].start);
+                }
+                start = start.max(other.ranges[k].end);
+                k += 1;
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    /// Values below `at`, and the others
+    pub fn split(&self, at: T) -> (Self, Self) {
+        let index = self.position(at);
+        let mut below = self.ranges[..index].to_vec();
+        let mut above = self.ranges[index..].to_vec();
+        if let Some(range) = above.first_mut() {
+            if range.start < at {
+                below.push(range.start..at);
+                range.start = at;
+            }
+        }
+        (IntervalSet { ranges: below }, IntervalSet { ranges: above })
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord + Add<Output = T> + From<u8>,
+{
+    pub fn insert_inclusive(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        self.insert(start..end + T::from(1));
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord + Sub<Output = T> + Sum,
+{
+    /// Number of values in the set
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .map(|range| range.end - range.start)
+            .sum()
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// Image of the set by a piecewise translation
+    ///
+    /// Each piece `(source, destination)` moves the values of `source` so that it starts at
+    /// `destination`; values in no source are left as they are, and those in several sources are
+    /// moved by the first one.
+    pub fn map_pieces(&self, pieces: &[(Range<T>, T)]) -> Self {
+        let mut remaining = self.clone();
+        let mut ranges = Vec::new();
+        for (source, destination) in pieces {
+            let moved = remaining.intersection(&IntervalSet::from(source.clone()));
+            for range in moved.ranges {
+                let start = range.start - source.start + *destination;
+                let end = range.end - source.start + *destination;
+                ranges.push(start..end);
+            }
+            remaining.remove(source.clone());
+        }
+        ranges.extend(remaining.ranges);
+        ranges.into_iter().collect()
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut ranges: Vec<Range<T>> = iter.into_iter().filter(|r| !r.is_empty()).collect();
+        ranges.sort_unstable_by_key(|range| range.start);
+        // merge in a single pass rather than with repeated insertions
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        IntervalSet { ranges: merged }
+    }
+}
+
+impl<T: Copy + Ord> Extend<Range<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Values of `set` in `0..64`, one bit each
+    fn bits(set: &IntervalSet<u32>) -> u64 {
+        (0..64)
+            .filter(|&v| set.contains(v))
+            .fold(0, |bits, v| bits | 1 << v)
+    }
+
+    fn random_set(seed: &mut u64) -> IntervalSet<u32> {
+        let mut next = || {
+            *seed ^= *seed << 13;
+            *seed ^= *seed >> 7;
+            *seed ^= *seed << 17;
+            (*seed % 64) as u32
+        };
+        let mut set = IntervalSet::new();
+        for _ in 0..next() % 6 {
+            let (a, b) = (next(), next());
+            if a % 3 == 0 {
+                set.remove(a.min(b)..a.max(b));
+            } else {
+                set.insert(a.min(b)..a.max(b));
+            }
+        }
+        set
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut set = IntervalSet::new();
+        set.insert(10..20);
+        set.insert(30..40);
+        set.insert(20..25);
+        assert_eq!(set.ranges(), [10..25, 30..40]);
+        set.insert_inclusive(25..=29);
+        assert_eq!(set, IntervalSet::from(10..40));
+        set.remove(15..17);
+        set.remove(39..50);
+        assert_eq!(set.ranges(), [10..15, 17..39]);
+        assert_eq!(set.len(), 27);
+        assert_eq!(set.min(), Some(10));
+        assert!(set.contains(10) && !set.contains(15) && !set.contains(39));
+        assert!(set.contains_range(20..39) && !set.contains_range(14..18));
+        assert!(set.overlaps(14..18) && !set.overlaps(15..17));
+        assert_eq!(set.split(20).0.ranges(), [10..15, 17..20]);
+        assert_eq!(set.split(20).1, IntervalSet::from(20..39));
+    }
+
+    #[test]
+    fn test_algebra() {
+        let mut seed = 0x2545f4914f6cdd1d;
+        for _ in 0..500 {
+            let (a, b) = (random_set(&mut seed), random_set(&mut seed));
+            assert_eq!(bits(&a.union(&b)), bits(&a) | bits(&b));
+            assert_eq!(bits(&a.intersection(&b)), bits(&a) & bits(&b));
+            assert_eq!(bits(&a.difference(&b)), bits(&a) & !bits(&b));
+            // the representation is normalized
+            assert_eq!(a.union(&b), b.union(&a));
+            assert_eq!(a.union(&b).len(), bits(&a.union(&b)).count_ones());
+            let reinserted: IntervalSet<u32> = a.ranges().iter().cloned().rev().collect();
+            assert_eq!(reinserted, a);
+        }
+    }
+
+    #[test]
+    fn test_map_pieces() {
+        // the seed-to-soil map of 2023 day 5
+        let pieces = [(98..100, 50), (50..98, 52)];
+        let set = IntervalSet::from(79..93).union(&IntervalSet::from(97..101));
+        let mapped = set.map_pieces(&pieces);
+        assert_eq!(mapped.ranges(), [50..52, 81..95, 99..101]);
+        // first piece wins
+        let set = IntervalSet::from(0..10);
+        assert_eq!(
+            set.map_pieces(&[(0..5, 100), (0..10, 200)]).ranges(),
+            [100..105, 205..210]
+        );
+    }
+}
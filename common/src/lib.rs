@@ -0,0 +1,12 @@
+pub mod animation;
+pub mod cuboid;
+pub mod cut;
+pub mod cycle;
+pub mod dot;
+pub mod error;
+pub mod grid;
+pub mod interval;
+pub mod ocr;
+pub mod search;
+pub mod solution;
+pub mod trace;
@@ -0,0 +1,205 @@
+use crate::grid::Grid;
+
+struct Font {
+    height: usize,
+    /// Width of a letter, including the spacing before the next one
+    width: usize,
+    /// Letters are drawn with `#` and `.`, with the trailing spacing omitted
+    glyphs: &'static [(char, &'static str)],
+}
+
+/// Font of most puzzles, with letters 6 pixels high
+const SMALL: Font = Font {
+    height: 6,
+    width: 5,
+    glyphs: &[
+        ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+        ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+        ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+        ('E', "####\n#...\n###.\n#...\n#...\n####"),
+        ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+        ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+        ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+        ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+        ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+        ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+        ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+        ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+        ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+        ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+        ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+        ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+        ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+        ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+    ],
+};
+
+/// Font with letters 10 pixels high, as in 2018 day 10
+const LARGE: Font = Font {
+    height: 10,
+    width: 8,
+    glyphs: &[
+        (
+            'A',
+            "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+        ),
+        (
+            'B',
+            "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+        ),
+        (
+            'C',
+            ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+        ),
+        (
+            'E',
+            "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+        ),
+        (
+            'F',
+            "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+        ),
+        (
+            'G',
+            ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+        ),
+        (
+            'H',
+            "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+        ),
+        (
+            'J',
+            "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+        ),
+        (
+            'K',
+            "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+        ),
+        (
+            'L',
+            "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+        ),
+        (
+            'N',
+            "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+        ),
+        (
+            'P',
+            "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+        ),
+        (
+            'R',
+            "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+        ),
+        (
+            'X',
+            "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+        ),
+        (
+            'Z',
+            "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+        ),
+    ],
+};
+
+impl Font {
+    fn matches(&self, pixels: &Grid<bool>, left: usize, glyph: &str) -> bool {
+        glyph.lines().enumerate().all(|(i, row)| {
+            let row = row.as_bytes();
+            (0..self.width).all(|j| {
+                let expected = row.get(j) == Some(&b'#');
+                let pixel = pixels.get(i as isize, (left + j) as isize) == Some(&true);
+                pixel == expected
+            })
+        })
+    }
+
+    fn read(&self, pixels: &Grid<bool>) -> Option<String> {
+        let mut letters = String::new();
+        for left in (0..pixels.cols()).step_by(self.width) {
+            let empty = (0..self.height)
+                .all(|i| (left..pixels.cols().min(left + self.width)).all(|j| !pixels[(i, j)]));
+            if empty {
+                continue;
+            }
+            let &(letter, _) = self
+                .glyphs
+                .iter()
+                .find(|(_, glyph)| self.matches(pixels, left, glyph))?;
+            letters.push(letter);
+        }
+        Some(letters)
+    }
+}
+
+/// Recognize the capital letters drawn by the lit pixels, or return `None` if some are unknown
+pub fn recognize(pixels: &Grid<bool>) -> Option<String> {
+    let font = [SMALL, LARGE]
+        .into_iter()
+        .find(|font| font.height == pixels.rows())?;
+    font.read(pixels)
+}
+
+/// Same as `recognize`, but from ASCII art with `#` for lit pixels, and `.` or spaces otherwise;
+/// when the letters cannot be recognized, the art is returned as is
+pub fn read_letters(art: &str) -> String {
+    let lit = |c| match c {
+        b'#' => Some(true),
+        b'.' | b' ' => Some(false),
+        _ => None,
+    };
+    Grid::parse_with(art, "a pixel", lit)
+        .ok()
+        .and_then(|pixels| recognize(&pixels))
+        .filter(|letters| !letters.is_empty())
+        .unwrap_or_else(|| art.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(font: &Font, text: &str) -> String {
+        let mut rows = vec![String::new(); font.height];
+        for c in text.chars() {
+            let (_, glyph) = font
+                .glyphs
+                .iter()
+                .find(|&&(letter, _)| letter == c)
+                .unwrap();
+            for (row, line) in rows.iter_mut().zip(glyph.lines()) {
+                row.push_str(&format!("{line:.<width$}", width = font.width));
+            }
+        }
+        rows.iter().map(|row| format!("{row}\n")).collect()
+    }
+
+    #[test]
+    fn test_small() {
+        let art = draw(&SMALL, "ABCEFGHIJKLOPRSUYZ");
+        assert_eq!(read_letters(&art), "ABCEFGHIJKLOPRSUYZ");
+        // trailing spacing may be missing or longer
+        let art = draw(&SMALL, "HI");
+        let trimmed: String = art
+            .lines()
+            .map(|line| format!("{}\n", &line[..9]))
+            .collect();
+        assert_eq!(read_letters(&trimmed), "HI");
+        let padded: String = art.lines().map(|line| format!("{line}.......\n")).collect();
+        assert_eq!(read_letters(&padded), "HI");
+    }
+
+    #[test]
+    fn test_large() {
+        let art = draw(&LARGE, "ABCEFGHJKLNPRXZ");
+        assert_eq!(read_letters(&art).as_str(), "ABCEFGHJKLNPRXZ");
+    }
+
+    #[test]
+    fn test_fallback() {
+        let art = "#..#\n.##.\n#..#\n#..#\n.##.\n#..#\n";
+        assert_eq!(read_letters(art), art);
+        let art = "#.\n.#\n";
+        assert_eq!(read_letters(art), art);
+    }
+}
@@ -0,0 +1,383 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Outcome of a search from one or several starting states
+#[derive(Clone, Debug)]
+pub struct Search<S, C> {
+    /// Optimal cost of every settled state
+    pub costs: HashMap<S, C>,
+    /// For each state, all the states from which it is reached with an optimal cost
+    pub predecessors: HashMap<S, Vec<S>>,
+    /// Goals reached with the optimal cost, in the order they were settled
+    pub goals: Vec<S>,
+}
+
+impl<S, C> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy,
+{
+    fn new() -> Self {
+        Search {
+            costs: HashMap::new(),
+            predecessors: HashMap::new(),
+            goals: Vec::new(),
+        }
+    }
+
+    pub fn goal(&self) -> Option<&S> {
+        self.goals.first()
+    }
+
+    pub fn goal_cost(&self) -> Option<C> {
+        self.cost(self.goal()?)
+    }
+
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.costs.get(state).copied()
+    }
+
+    /// One optimal path from a start to `state`, both included
+    pub fn path(&self, state: &S) -> Option<Vec<S>> {
+        self.costs.get(state)?;
+        let mut path = vec![state.clone()];
+        while let Some(previous) = self
+            .predecessors
+            .get(path.last().unwrap())
+            .and_then(|predecessors| predecessors.first())
+        {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// All the optimal paths from a start to `state`; there can be exponentially many
+    pub fn all_paths(&self, state: &S) -> Vec<Vec<S>> {
+        if !self.costs.contains_key(state) {
+            return Vec::new();
+        }
+        let Some(predecessors) = self.predecessors.get(state) else {
+            return vec![vec![state.clone()]];
+        };
+        let mut paths = Vec::new();
+        for previous in predecessors {
+            for mut path in self.all_paths(previous) {
+                path.push(state.clone());
+                paths.push(path);
+            }
+        }
+        paths
+    }
+
+    /// States that are part of at least one optimal path to one of `targets`
+    pub fn on_optimal_paths<'a>(&self, targets: impl IntoIterator<Item = &'a S>) -> HashSet<S>
+    where
+        S: 'a,
+    {
+        let mut seen = HashSet::new();
+        let mut stack: Vec<S> = targets
+            .into_iter()
+            .filter(|state| self.costs.contains_key(state))
+            .cloned()
+            .collect();
+        while let Some(state) = stack.pop() {
+            if !seen.insert(state.clone()) {
+                continue;
+            }
+            if let Some(predecessors) = self.predecessors.get(&state) {
+                stack.extend(predecessors.iter().cloned());
+            }
+        }
+        seen
+    }
+}
+
+/// Breadth-first search where every transition costs 1
+///
+/// The search stops once the goals at the smallest distance have been reached; use `|_| false`
+/// as `is_goal` to explore every reachable state.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::new();
+    let mut q = VecDeque::new();
+    for start in starts {
+        if !search.costs.contains_key(&start) {
+            search.costs.insert(start.clone(), 0);
+            q.push_back((0, start));
+        }
+    }
+    while let Some((distance, state)) = q.pop_front() {
+        if let Some(goal) = search.goal() {
+            if distance > search.costs[goal] {
+                break;
+            }
+        }
+        if is_goal(&state) {
+            search.goals.push(state.clone());
+        }
+        for next in successors(&state) {
+            match search.costs.get(&next) {
+                None => {
+                    search.costs.insert(next.clone(), distance + 1);
+                    search
+                        .predecessors
+                        .insert(next.clone(), vec![state.clone()]);
+                    q.push_back((distance + 1, next));
+                }
+                Some(&d) if d == distance + 1 => {
+                    search
+                        .predecessors
+                        .get_mut(&next)
+                        .unwrap()
+                        .push(state.clone());
+                }
+                Some(_) => (),
+            }
+        }
+    }
+    // forget the states that were discovered but not settled
+    if let Some(goal) = search.goal() {
+        let max_distance = search.costs[goal];
+        search.costs.retain(|_, &mut d| d <= max_distance);
+        let costs = &search.costs;
+        search
+            .predecessors
+            .retain(|state, _| costs.contains_key(state));
+    }
+    search
+}
+
+/// Dijkstra's algorithm, for non-negative costs
+///
+/// The search stops once the goals with the smallest cost have been reached; use `|_| false` as
+/// `is_goal` to explore every reachable state.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+struct Candidate<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+// NOTE: inverted ordering to make it a min-heap
+impl<S, C: Ord> PartialEq for Candidate<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Candidate<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Candidate<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> Ord for Candidate<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// A* search, for non-negative costs and a consistent `heuristic` (a lower bound of the remaining
+/// cost to a goal that never decreases by more than the cost of a transition)
+///
+/// The search stops once the goals with the smallest cost have been reached.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut search = Search::new();
+    let mut best = HashMap::new();
+    let mut q = BinaryHeap::new();
+    let starts: HashSet<S> = starts.into_iter().collect();
+    for start in starts.iter().cloned() {
+        best.insert(start.clone(), C::default());
+        q.push(Candidate {
+            priority: heuristic(&start),
+            cost: C::default(),
+            state: start,
+        });
+    }
+    let mut goal_cost = None;
+    while let Some(Candidate { cost, state, .. }) = q.pop() {
+        if search.costs.contains_key(&state) || cost > best[&state] {
+            continue;
+        }
+        if goal_cost.is_some_and(|goal_cost| cost > goal_cost) {
+            break;
+        }
+        search.costs.insert(state.clone(), cost);
+        if is_goal(&state) {
+            goal_cost = Some(cost);
+            search.goals.push(state.clone());
+        }
+        for (next, step_cost) in successors(&state) {
+            // with zero costs, a settled state or a start could otherwise end up in a cycle of
+            // predecessors
+            if search.costs.contains_key(&next) || starts.contains(&next) {
+                continue;
+            }
+            let next_cost = cost + step_cost;
+            match best.get(&next) {
+                Some(&c) if c < next_cost => continue,
+                Some(&c) if c == next_cost => {
+                    search
+                        .predecessors
+                        .entry(next)
+                        .or_insert_with(Vec::new)
+                        .push(state.clone());
+                    continue;
+                }
+                _ => (),
+            }
+            best.insert(next.clone(), next_cost);
+            search
+                .predecessors
+                .insert(next.clone(), vec![state.clone()]);
+            q.push(Candidate {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+    // forget the states that were discovered but not settled
+    let costs = &search.costs;
+    search
+        .predecessors
+        .retain(|state, _| costs.contains_key(state));
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 - 1 - 2
+    // |       |
+    // 3 - 4 - 5 - 6
+    fn neighbors(&n: &u32) -> Vec<u32> {
+        match n {
+            0 => vec![1, 3],
+            1 => vec![0, 2],
+            2 => vec![1, 5],
+            3 => vec![0, 4],
+            4 => vec![3, 5],
+            5 => vec![2, 4, 6],
+            6 => vec![5],
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let search = bfs([0], neighbors, |&n| n == 5);
+        assert_eq!(search.goal_cost(), Some(3));
+        assert_eq!(search.all_paths(&5), [vec![0, 1, 2, 5], vec![0, 3, 4, 5]]);
+        assert_eq!(search.on_optimal_paths(&[5]).len(), 6);
+        assert_eq!(search.cost(&6), None);
+
+        let search = bfs([0], neighbors, |_| false);
+        assert_eq!(search.goal(), None);
+        assert_eq!(search.cost(&6), Some(4));
+
+        let search = bfs([1, 4], neighbors, |&n| n == 2 || n == 5);
+        assert_eq!(search.goals, [2, 5]);
+        assert_eq!(search.path(&5), Some(vec![4, 5]));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // going through 3 and 4 is more expensive
+        let successors = |n: &u32| {
+            neighbors(n)
+                .into_iter()
+                .map(|m| (m, if *n == 3 || m == 3 { 5 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+        let search = dijkstra([0], successors, |&n| n == 6);
+        assert_eq!(search.goal_cost(), Some(4));
+        assert_eq!(search.path(&6), Some(vec![0, 1, 2, 5, 6]));
+        assert_eq!(search.all_paths(&6).len(), 1);
+
+        let search = dijkstra([0], successors, |_| false);
+        assert_eq!(search.cost(&4), Some(4));
+        assert_eq!(search.predecessors[&4], [5]);
+    }
+
+    #[test]
+    fn test_astar() {
+        // grid with a wall in the middle column, except at the bottom
+        let size = 5;
+        let successors = |&(i, j): &(i32, i32)| {
+            [(i - 1, j), (i + 1, j), (i, j - 1), (i, j + 1)]
+                .into_iter()
+                .filter(|&(i, j)| (0..size).contains(&i) && (0..size).contains(&j))
+                .filter(|&(i, j)| j != 2 || i == size - 1)
+                .map(|state| (state, 1))
+                .collect::<Vec<_>>()
+        };
+        let goal = (0, size - 1);
+        let heuristic = |&(i, j): &(i32, i32)| i.abs_diff(goal.0) + j.abs_diff(goal.1);
+        let search = astar(
+            [(0, 0)],
+            successors,
+            |s| heuristic(s) as i32,
+            |&s| s == goal,
+        );
+        assert_eq!(search.goal_cost(), Some(12));
+        assert_eq!(search.path(&goal).unwrap().len(), 13);
+        let search = dijkstra([(0, 0)], successors, |&s| s == goal);
+        assert_eq!(search.goal_cost(), Some(12));
+    }
+
+    #[test]
+    fn test_zero_costs() {
+        // 0 and 1 are the same place, and so are 2 and 3
+        let successors = |&n: &u32| match n {
+            0 => vec![(1, 0)],
+            1 => vec![(0, 0), (2, 1)],
+            2 => vec![(1, 1), (3, 0)],
+            3 => vec![(2, 0)],
+            _ => unreachable!(),
+        };
+        let search = dijkstra([0], successors, |&n| n == 3);
+        assert_eq!(search.path(&3), Some(vec![0, 1, 2, 3]));
+        assert_eq!(search.all_paths(&3).len(), 1);
+        assert!(!search.predecessors.contains_key(&0));
+
+        let search = dijkstra([0, 1], successors, |_| false);
+        assert_eq!(search.all_paths(&3), [vec![1, 2, 3]]);
+        assert!(!search.predecessors.contains_key(&1));
+    }
+}
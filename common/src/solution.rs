@@ -0,0 +1,244 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::error::{ParseError, SolveError};
+
+/// An extra argument taken by a part, on top of the puzzle input
+#[derive(Clone, Copy, Debug)]
+pub struct Parameter {
+    pub name: &'static str,
+    /// Value used for the actual puzzle input
+    pub default: &'static str,
+    check: fn(&str) -> bool,
+}
+
+fn is_valid<T: FromStr>(value: &str) -> bool {
+    value.parse::<T>().is_ok()
+}
+
+impl Parameter {
+    pub const fn new<T: FromStr>(name: &'static str, default: &'static str) -> Self {
+        Parameter {
+            name,
+            default,
+            check: is_valid::<T>,
+        }
+    }
+}
+
+/// Values of the parameters of a part, validated against their declaration
+#[derive(Debug, Default)]
+pub struct Parameters {
+    values: BTreeMap<&'static str, String>,
+}
+
+impl Parameters {
+    /// Assign `values` positionally, falling back to the default for the missing ones
+    pub fn new(declared: &[Parameter], values: &[String]) -> Result<Self, String> {
+        if values.len() > declared.len() {
+            return Err(format!("unexpected parameter {:?}", values[declared.len()]));
+        }
+        let mut ret = Parameters::default();
+        for (i, parameter) in declared.iter().enumerate() {
+            let value = values
+                .get(i)
+                .map(String::as_str)
+                .unwrap_or(parameter.default);
+            if !(parameter.check)(value) {
+                return Err(format!("invalid {} {:?}", parameter.name, value));
+            }
+            ret.values.insert(parameter.name, value.to_string());
+        }
+        Ok(ret)
+    }
+
+    pub fn get<T: FromStr>(&self, name: &str) -> T {
+        let value = self
+            .values
+            .get(name)
+            .unwrap_or_else(|| panic!("undeclared parameter {name}"));
+        match value.parse() {
+            Ok(value) => value,
+            Err(_) => panic!("parameter {name} declared with another type"),
+        }
+    }
+}
+
+/// Result of a part, which fails when the input does not have the expected shape
+pub type Answer<T> = Result<T, SolveError>;
+
+pub trait Solution {
+    const YEAR: u32;
+    const DAY: u32;
+    const PART1_PARAMETERS: &'static [Parameter] = &[];
+    const PART2_PARAMETERS: &'static [Parameter] = &[];
+
+    /// Representation of the puzzle input shared by both parts
+    type Input<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+    fn part1(input: &Self::Input<'_>, params: &Parameters) -> Answer<impl Display>;
+    /// Day 25 only has one part
+    fn part2(_input: &Self::Input<'_>, _params: &Parameters) -> Option<Answer<impl Display>> {
+        None::<Answer<&str>>
+    }
+}
+
+/// Type-erased entry for a `Solution`, so that all the days can be stored together
+#[derive(Clone, Copy)]
+pub struct Entry {
+    pub year: u32,
+    pub day: u32,
+    parameters: [&'static [Parameter]; 2],
+    run: fn(&str, u32, &Parameters) -> Result<Option<String>, String>,
+}
+
+fn run<S: Solution>(input: &str, part: u32, params: &Parameters) -> Result<Option<String>, String> {
+    let input = S::parse(input).map_err(|e| e.in_puzzle(S::YEAR, S::DAY).to_string())?;
+    let answer = match part {
+        1 => Some(S::part1(&input, params).map(|answer| answer.to_string())),
+        2 => S::part2(&input, params).map(|answer| answer.map(|answer| answer.to_string())),
+        _ => None,
+    };
+    answer
+        .transpose()
+        .map_err(|e| e.in_puzzle(S::YEAR, S::DAY).to_string())
+}
+
+impl Entry {
+    pub fn new<S: Solution>() -> Self {
+        Entry {
+            year: S::YEAR,
+            day: S::DAY,
+            parameters: [S::PART1_PARAMETERS, S::PART2_PARAMETERS],
+            run: run::<S>,
+        }
+    }
+
+    pub fn parameters(&self, part: u32) -> &'static [Parameter] {
+        match part {
+            1 | 2 => self.parameters[part as usize - 1],
+            _ => &[],
+        }
+    }
+
+    /// Solve the given part, with `values` overriding the default parameters
+    pub fn run(&self, input: &str, part: u32, values: &[String]) -> Result<String, String> {
+        let params = Parameters::new(self.parameters(part), values)?;
+        (self.run)(input, part, &params)?
+            .ok_or_else(|| format!("no solution for {} day {} part {part}", self.year, self.day))
+    }
+}
+
+#[derive(Default)]
+pub struct Registry {
+    entries: BTreeMap<(u32, u32), Entry>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Registry::default()
+    }
+
+    pub fn register<S: Solution>(&mut self) {
+        let previous = self.entries.insert((S::YEAR, S::DAY), Entry::new::<S>());
+        assert!(
+            previous.is_none(),
+            "{} day {} registered twice",
+            S::YEAR,
+            S::DAY
+        );
+    }
+
+    pub fn get(&self, year: u32, day: u32) -> Option<&Entry> {
+        self.entries.get(&(year, day))
+    }
+
+    /// Iterate over the solutions by chronological order
+    pub fn iter(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const YEAR: u32 = 2000;
+        const DAY: u32 = 1;
+        const PART2_PARAMETERS: &'static [Parameter] = &[Parameter::new::<i64>("factor", "2")];
+
+        type Input<'a> = Vec<i64>;
+
+        fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+            let mut ret = Vec::new();
+            for (i, line) in input.lines().enumerate() {
+                ret.push(crate::error::parse(i + 1, line, "a number")?);
+            }
+            Ok(ret)
+        }
+
+        fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+            Ok(input.iter().sum::<i64>())
+        }
+
+        fn part2(input: &Self::Input<'_>, params: &Parameters) -> Option<Answer<impl Display>> {
+            let factor: i64 = params.get("factor");
+            if factor == 0 {
+                return Some(Err(SolveError::new("the factor cannot be 0")));
+            }
+            Some(Ok(factor * input.iter().sum::<i64>()))
+        }
+    }
+
+    struct Single;
+
+    impl Solution for Single {
+        const YEAR: u32 = 2000;
+        const DAY: u32 = 25;
+
+        type Input<'a> = &'a str;
+
+        fn parse(input: &str) -> Result<&str, ParseError> {
+            Ok(input)
+        }
+
+        fn part1(input: &Self::Input<'_>, _params: &Parameters) -> Answer<impl Display> {
+            Ok(input.len())
+        }
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = Registry::new();
+        registry.register::<Single>();
+        registry.register::<Sum>();
+        let days: Vec<u32> = registry.iter().map(|entry| entry.day).collect();
+        assert_eq!(days, [1, 25]);
+
+        let sum = registry.get(2000, 1).unwrap();
+        assert_eq!(sum.run("1\n2\n3", 1, &[]).unwrap(), "6");
+        assert_eq!(sum.run("1\n2\n3", 2, &[]).unwrap(), "12");
+        assert_eq!(sum.run("1\n2\n3", 2, &["3".to_string()]).unwrap(), "18");
+        assert!(sum.run("1\n2\n3", 2, &["three".to_string()]).is_err());
+        assert_eq!(
+            sum.run("1\n2\n3", 2, &["0".to_string()]).unwrap_err(),
+            "2000 day 1: the factor cannot be 0"
+        );
+        assert!(sum.run("1\n2\n3", 1, &["3".to_string()]).is_err());
+        assert!(sum.run("1\n2\n3", 3, &[]).is_err());
+        assert_eq!(
+            sum.run("1\nx\n3", 1, &[]).unwrap_err(),
+            "2000 day 1, line 2: expected a number, found \"x\""
+        );
+
+        let single = registry.get(2000, 25).unwrap();
+        assert_eq!(single.run("abc", 1, &[]).unwrap(), "3");
+        assert!(single.run("abc", 2, &[]).is_err());
+        assert!(registry.get(2000, 2).is_none());
+    }
+}
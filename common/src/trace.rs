@@ -0,0 +1,90 @@
+use std::cell::RefCell;
+use std::fmt::Arguments;
+use std::rc::Rc;
+
+type Sink = Box<dyn FnMut(&str)>;
+
+thread_local! {
+    static SINK: RefCell<Option<Sink>> = const { RefCell::new(None) };
+}
+
+/// Send the messages traced on this thread while running `f` to `sink`
+pub fn with_sink<R>(sink: impl FnMut(&str) + 'static, f: impl FnOnce() -> R) -> R {
+    let previous = SINK.with(|current| current.replace(Some(Box::new(sink))));
+    // restore the previous sink even if `f` panics
+    struct Restore(Option<Sink>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SINK.with(|current| *current.borrow_mut() = self.0.take());
+        }
+    }
+    let _restore = Restore(previous);
+    f()
+}
+
+/// Run `f` and collect the messages it traced
+pub fn capture<R>(f: impl FnOnce() -> R) -> (R, Vec<String>) {
+    let messages = Rc::new(RefCell::new(Vec::new()));
+    let sink = {
+        let messages = Rc::clone(&messages);
+        move |message: &str| messages.borrow_mut().push(message.to_string())
+    };
+    let ret = with_sink(sink, f);
+    let messages = messages.take();
+    (ret, messages)
+}
+
+/// Whether a sink is installed, i.e. whether tracing is worth the effort
+pub fn enabled() -> bool {
+    SINK.with(|current| current.borrow().is_some())
+}
+
+/// Implementation of `trace!`
+pub fn emit(args: Arguments<'_>) {
+    // take the sink out while it runs, so that tracing from the sink itself is ignored
+    let Some(mut sink) = SINK.with(|current| current.borrow_mut().take()) else {
+        return;
+    };
+    sink(&args.to_string());
+    SINK.with(|current| *current.borrow_mut() = Some(sink));
+}
+
+/// Like `println!`, but to the sink installed on the current thread with `trace::with_sink`
+///
+/// Without a sink, nothing happens; in particular, the arguments are not formatted.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::trace::enabled() {
+            $crate::trace::emit(format_args!($($arg)*));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace() {
+        // disabled by default
+        let mut formatted = false;
+        let mut argument = || {
+            formatted = true;
+            0
+        };
+        crate::trace!("{}", argument());
+        assert!(!formatted);
+
+        let (answer, messages) = capture(|| {
+            crate::trace!("a = {}", 1);
+            let (_, inner) = capture(|| crate::trace!("b"));
+            assert_eq!(inner, ["b"]);
+            crate::trace!("c");
+            42
+        });
+        assert_eq!(answer, 42);
+        assert_eq!(messages, ["a = 1", "c"]);
+        assert!(!enabled());
+    }
+}